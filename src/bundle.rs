@@ -0,0 +1,69 @@
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// File name of the index written next to exported bundles
+pub const INDEX_FILE: &str = "index.toml";
+
+/// The index of a directory of exported bundles,
+/// recording where each bundle came from so it can be restored.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct BundleIndex {
+    #[serde(default)]
+    pub bundles: Vec<BundleEntry>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BundleEntry {
+    /// Path of the bundle, relative to the index
+    pub file: String,
+    /// The original remote URL of the repo
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    pub provider: String,
+    pub owner: String,
+    pub repo: String,
+}
+
+impl BundleIndex {
+    pub fn load(path: &Path) -> Result<Self, Box<dyn std::error::Error>> {
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| format!("unable to read bundle index {}: {}", path.display(), e))?;
+        let index = toml::from_str(&contents)
+            .map_err(|e| format!("invalid bundle index {}: {}", path.display(), e))?;
+        Ok(index)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+        let contents = toml::to_string_pretty(self)?;
+        std::fs::write(path, contents)
+            .map_err(|e| format!("unable to write bundle index {}: {}", path.display(), e))?;
+        Ok(())
+    }
+}
+
+/// Write a bundle with all refs of the repo at `repo_path`.
+///
+/// libgit2 can't write bundles, so this uses the `git` command.
+pub fn create_bundle(repo_path: &Path, bundle_path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    crate::trace!("bundling {} into {}", repo_path.display(), bundle_path.display());
+    if let Some(parent) = bundle_path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let output = std::process::Command::new("git")
+        .arg("-C")
+        .arg(repo_path)
+        .args(["bundle", "create", "--quiet"])
+        .arg(bundle_path)
+        .arg("--all")
+        .output()
+        .map_err(|e| format!("unable to run git: {}", e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "git bundle of {} failed: {}",
+            repo_path.display(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        )
+        .into());
+    }
+    Ok(())
+}
@@ -0,0 +1,147 @@
+use std::{
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use crate::{commands::clone::canonical_url, config::AppConfig, trace};
+
+/// File in each mirror that is touched whenever the mirror is used
+const LAST_USED_FILE: &str = "gclone-last-used";
+
+/// Name of the remote of a mirror, whatever `--origin` the clones from it use
+pub const MIRROR_REMOTE: &str = "origin";
+
+/// The local object cache, a directory of bare mirrors keyed by canonical URL,
+/// ie. `<cache>/mirrors/github.com/owner/repo.git`
+pub struct Cache {
+    root: PathBuf,
+}
+
+/// A bare mirror in the object cache
+pub struct Mirror {
+    /// The upstream URL of the mirror
+    pub url: String,
+    pub path: PathBuf,
+    /// Size on disk in bytes
+    pub size: u64,
+    pub last_used: Option<SystemTime>,
+}
+
+impl Cache {
+    pub fn new(root: PathBuf) -> Self {
+        Self { root }
+    }
+
+    pub fn from_config(config: &AppConfig) -> Self {
+        Self::new(config.get_cache_dir())
+    }
+
+    pub fn mirrors_dir(&self) -> PathBuf {
+        self.root.join("mirrors")
+    }
+
+    /// Get the path of the mirror for a repo URL
+    pub fn mirror_path(&self, url: &str) -> PathBuf {
+        self.mirrors_dir().join(format!("{}.git", canonical_url(url)))
+    }
+
+    /// Get the mirror for a repo URL, if it is cached
+    pub fn find(&self, url: &str) -> Option<PathBuf> {
+        let path = self.mirror_path(url);
+        if path.join("HEAD").exists() {
+            Some(path)
+        } else {
+            None
+        }
+    }
+
+    /// Mark a mirror as used, for `gclone cache prune`
+    pub fn touch(&self, mirror_path: &Path) -> std::io::Result<()> {
+        std::fs::write(mirror_path.join(LAST_USED_FILE), "")
+    }
+
+    /// List all mirrors in the cache
+    pub fn mirrors(&self) -> Vec<Mirror> {
+        #[cfg(feature = "logging")]
+        trace!("listing mirrors in {}", self.mirrors_dir().display());
+        let mut mirrors = Vec::new();
+        let mut dirs = vec![self.mirrors_dir()];
+        while let Some(dir) = dirs.pop() {
+            if dir.join("HEAD").exists() {
+                let url = git2::Repository::open_bare(&dir)
+                    .ok()
+                    .and_then(|repo| repo.config().ok())
+                    .and_then(|config| config.get_string(&format!("remote.{}.url", MIRROR_REMOTE)).ok())
+                    .unwrap_or_default();
+                let last_used = std::fs::metadata(dir.join(LAST_USED_FILE))
+                    .and_then(|m| m.modified())
+                    .ok();
+                mirrors.push(Mirror {
+                    url,
+                    size: dir_size(&dir),
+                    path: dir,
+                    last_used,
+                });
+                continue;
+            }
+            if let Ok(entries) = std::fs::read_dir(&dir) {
+                for entry in entries.flatten() {
+                    if entry.file_type().map(|t| t.is_dir()).unwrap_or(false) {
+                        dirs.push(entry.path());
+                    }
+                }
+            }
+        }
+        mirrors.sort_by(|a, b| a.path.cmp(&b.path));
+        mirrors
+    }
+
+    /// Remove a mirror, and any parent directories left empty
+    pub fn remove(&self, mirror: &Mirror) -> std::io::Result<()> {
+        std::fs::remove_dir_all(&mirror.path)?;
+        let root = self.mirrors_dir();
+        let mut dir = mirror.path.parent();
+        while let Some(parent) = dir {
+            if parent == root || std::fs::remove_dir(parent).is_err() {
+                break;
+            }
+            dir = parent.parent();
+        }
+        Ok(())
+    }
+}
+
+/// Get the total size of all files in a directory
+pub fn dir_size(path: &Path) -> u64 {
+    let mut size = 0;
+    let mut dirs = vec![path.to_path_buf()];
+    while let Some(dir) = dirs.pop() {
+        let entries = match std::fs::read_dir(&dir) {
+            Ok(entries) => entries,
+            Err(_) => continue,
+        };
+        for entry in entries.flatten() {
+            match entry.metadata() {
+                Ok(m) if m.is_dir() => dirs.push(entry.path()),
+                Ok(m) => size += m.len(),
+                Err(_) => {}
+            }
+        }
+    }
+    size
+}
+
+/// Format a size in bytes for humans, ie. `1536` -> `1.5 KiB`
+pub fn format_size(size: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if size < 1024 {
+        return format!("{} B", size);
+    }
+    let mut value = size as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", value, UNITS[unit])
+}
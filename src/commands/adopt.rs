@@ -0,0 +1,144 @@
+#[cfg(feature = "cli")]
+use {
+    crate::{
+        commands::{clone, CliCommand},
+        config::{self, AppConfig},
+        registry::{Registry, RegistryEntry},
+        repos,
+    },
+    std::{
+        collections::BTreeSet,
+        path::{Path, PathBuf},
+    },
+};
+
+#[cfg(feature = "cli")]
+#[derive(Debug, clap::Args)]
+pub struct AdoptCommand {
+    /// Directories to scan for repos, recursively
+    #[clap(required = true)]
+    dirs: Vec<String>,
+
+    /// The template path the be used
+    #[clap(long, short, default_value_t = config::get_config().default_template)]
+    template: String,
+
+    /// leave a symlink to the new location behind
+    #[clap(long)]
+    link: bool,
+
+    // global flags
+    #[clap(from_global)]
+    yes: bool,
+}
+
+#[cfg(feature = "cli")]
+impl CliCommand for AdoptCommand {
+    fn command(self, config: AppConfig, dry_run: bool) -> Result<(), Box<dyn std::error::Error>> {
+        crate::trace!("Adopt");
+        let template = config.get_template(&self.template);
+        let cwd = std::env::current_dir()?;
+
+        // plan the moves, skipping repos that can't be moved
+        let mut plan: Vec<(PathBuf, PathBuf, String)> = Vec::new();
+        let mut targets = BTreeSet::new();
+        for dir in &self.dirs {
+            let dir = Path::new(dir).canonicalize().map_err(|e| format!("unable to read {}: {}", dir, e))?;
+            for path in repos::find_repos(&dir) {
+                let origin = match repos::origin_url(&path) {
+                    Some(origin) => origin,
+                    None => {
+                        crate::warn!("skipping {}, it has no origin remote", path.display());
+                        continue;
+                    }
+                };
+                let meta = match clone::get_repo_meta(&origin, &clone::get_repo_type(&origin)) {
+                    Ok(meta) => meta,
+                    Err(e) => {
+                        crate::warn!("skipping {}, {}", path.display(), e);
+                        continue;
+                    }
+                };
+                let target = cwd.join(clone::build_target_path(&template, &meta));
+                if path == target || target.canonicalize().map(|t| t == path).unwrap_or(false) {
+                    crate::debug!("{} is already in place", path.display());
+                    continue;
+                }
+                if target.exists() || targets.contains(&target) {
+                    crate::warn!("skipping {}, {} already exists", path.display(), target.display());
+                    continue;
+                }
+                if target.starts_with(&path) {
+                    crate::warn!("skipping {}, {} is inside it", path.display(), target.display());
+                    continue;
+                }
+                targets.insert(target.clone());
+                plan.push((path, target, origin));
+            }
+        }
+
+        if plan.is_empty() {
+            crate::info!("Nothing to adopt");
+            return Ok(());
+        }
+        for (path, target, _) in &plan {
+            println!("{} -> {}", path.display(), target.display());
+        }
+        if dry_run {
+            crate::info!("Dry Run, not moving {} repos", plan.len());
+            return Ok(());
+        }
+        if !self.yes
+            && !dialoguer::Confirm::new()
+                .with_prompt(format!("You are about to move {} repos", plan.len()))
+                .interact()
+                .unwrap_or(false)
+        {
+            crate::info!("Not moving repos");
+            return Ok(());
+        }
+
+        let mut entries = Vec::new();
+        let mut failed = 0;
+        for (path, target, origin) in &plan {
+            if let Err(e) = repos::move_repo(path, target) {
+                crate::error!("unable to move {} to {}: {}", path.display(), target.display(), e);
+                failed += 1;
+                continue;
+            }
+            if self.link {
+                if let Err(e) = symlink(target, path) {
+                    crate::warn!("unable to link {} to {}: {}", path.display(), target.display(), e);
+                }
+            }
+            let branch = git2::Repository::open(target)
+                .ok()
+                .and_then(|repo| repo.head().ok().and_then(|h| h.shorthand().map(String::from)));
+            let entry = RegistryEntry::new(origin, &target.to_string_lossy(), Some(self.template.clone()), branch);
+            if let Ok(entry) = entry {
+                entries.push(entry);
+            }
+        }
+        Registry::update(|registry| {
+            for entry in entries {
+                registry.record(entry);
+            }
+        })?;
+
+        if failed > 0 {
+            return Err(format!("{} of {} repos failed to move", failed, plan.len()).into());
+        }
+        crate::info!("Adopted {} repos", plan.len());
+        Ok(())
+    }
+}
+
+#[cfg(all(feature = "cli", unix))]
+fn symlink(target: &Path, link: &Path) -> std::io::Result<()> {
+    std::os::unix::fs::symlink(target, link)
+}
+
+#[cfg(all(feature = "cli", windows))]
+fn symlink(target: &Path, link: &Path) -> std::io::Result<()> {
+    std::os::windows::fs::symlink_dir(target, link)
+}
@@ -0,0 +1,120 @@
+#[cfg(feature = "cli")]
+use {
+    crate::{
+        bundle::{self, BundleEntry, BundleIndex},
+        commands::{clone, CliCommand},
+        config::AppConfig,
+        repos,
+    },
+    std::path::{Path, PathBuf},
+};
+
+#[cfg(feature = "cli")]
+#[derive(Debug, clap::Args)]
+pub struct BundleCommand {
+    /// Repo paths, or patterns matched against the URLs of managed repos, eg. "github.com/acme/*"
+    #[clap(required = true)]
+    repos: Vec<String>,
+
+    /// Directory to write the bundles and their index to
+    #[clap(long, short)]
+    out: String,
+}
+
+#[cfg(feature = "cli")]
+impl CliCommand for BundleCommand {
+    fn command(self, config: AppConfig, dry_run: bool) -> Result<(), Box<dyn std::error::Error>> {
+        crate::trace!("Bundle");
+        let mut selected: Vec<PathBuf> = Vec::new();
+        let mut managed: Option<Vec<(PathBuf, String)>> = None;
+        for arg in &self.repos {
+            let path = Path::new(arg);
+            if repos::is_repo(path) {
+                selected.push(path.canonicalize()?);
+                continue;
+            }
+            let managed = managed.get_or_insert_with(|| {
+                repos::managed_repos(&config)
+                    .into_iter()
+                    .filter_map(|p| repos::origin_url(&p).map(|url| (p, url)))
+                    .collect()
+            });
+            let matched: Vec<PathBuf> = managed
+                .iter()
+                .filter(|(_, url)| repos::matches_pattern(arg, url))
+                .map(|(p, _)| p.clone())
+                .collect();
+            if matched.is_empty() {
+                crate::warn!("no repos matched {}", arg);
+            }
+            selected.extend(matched);
+        }
+        selected.sort();
+        selected.dedup();
+        if selected.is_empty() {
+            return Err("no repos to bundle".into());
+        }
+
+        let out = Path::new(&self.out);
+        let index_path = out.join(bundle::INDEX_FILE);
+        let mut index = if index_path.exists() {
+            BundleIndex::load(&index_path)?
+        } else {
+            BundleIndex::default()
+        };
+
+        for path in &selected {
+            let url = repos::origin_url(path);
+            let meta = url.as_ref().map(|url| clone::get_repo_meta(url, &clone::get_repo_type(url)));
+            let entry = match meta {
+                Some(Ok(meta)) => BundleEntry {
+                    file: format!("{}/{}/{}.bundle", meta.provider, meta.owner, meta.repo),
+                    url: url.clone(),
+                    provider: meta.provider,
+                    owner: meta.owner,
+                    repo: meta.repo,
+                },
+                // named after its path, keeping the origin to restore
+                meta => {
+                    match meta {
+                        Some(Err(e)) => {
+                            crate::warn!("{}: {}", path.display(), e);
+                        }
+                        _ => {
+                            crate::warn!("{} has no origin remote", path.display());
+                        }
+                    }
+                    let name = |p: Option<&Path>| {
+                        p.and_then(|p| p.file_name())
+                            .map(|n| n.to_string_lossy().to_string())
+                            .unwrap_or_default()
+                    };
+                    let (owner, repo) = (name(path.parent()), name(Some(path)));
+                    BundleEntry {
+                        file: format!("local/{}/{}.bundle", owner, repo),
+                        url: url.clone(),
+                        provider: "local".to_string(),
+                        owner,
+                        repo,
+                    }
+                }
+            };
+
+            if dry_run {
+                crate::info!("dry run: bundling {} to {}", path.display(), out.join(&entry.file).display());
+                continue;
+            }
+            crate::info!("bundling {} to {}", path.display(), out.join(&entry.file).display());
+            bundle::create_bundle(path, &out.join(&entry.file))?;
+            index.bundles.retain(|b| b.file != entry.file);
+            index.bundles.push(entry);
+        }
+
+        if !dry_run {
+            index.bundles.sort_by(|a, b| a.file.cmp(&b.file));
+            index.save(&index_path)?;
+            crate::info!("wrote {}", index_path.display());
+        }
+        Ok(())
+    }
+}
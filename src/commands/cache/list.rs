@@ -0,0 +1,37 @@
+#[cfg(feature = "cli")]
+#[derive(Debug, clap::Args)]
+pub struct ListCommand {}
+
+#[cfg(feature = "cli")]
+impl crate::commands::CliCommand for ListCommand {
+    fn command(
+        self,
+        config: crate::config::AppConfig,
+        _dry_run: bool,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        crate::trace!("Cache:List");
+        let cache = crate::cache::Cache::from_config(&config);
+        let mut table = prettytable::Table::new();
+        table.add_row(row!["URL", "Size", "Last Used", "Path"]);
+        for mirror in cache.mirrors() {
+            let last_used = mirror
+                .last_used
+                .and_then(|t| t.elapsed().ok())
+                .map(|d| match d.as_secs() / 86400 {
+                    0 => "today".to_string(),
+                    1 => "yesterday".to_string(),
+                    days => format!("{} days ago", days),
+                })
+                .unwrap_or_else(|| "never".to_string());
+            table.add_row(row![
+                mirror.url,
+                crate::cache::format_size(mirror.size),
+                last_used,
+                mirror.path.display()
+            ]);
+        }
+        table.printstd();
+
+        Ok(())
+    }
+}
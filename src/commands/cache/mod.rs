@@ -0,0 +1,45 @@
+#[cfg(feature = "cli")]
+use super::CliCommand;
+
+mod list;
+mod prune;
+mod size;
+
+#[cfg(feature = "cli")]
+#[derive(Debug, clap::Args)]
+#[clap(args_conflicts_with_subcommands = true)]
+pub struct CacheCommand {
+    #[clap(subcommand)]
+    command: Option<CacheCommands>,
+}
+
+#[cfg(feature = "cli")]
+#[derive(Debug, clap::Subcommand)]
+enum CacheCommands {
+    /// List the mirrors in the cache
+    #[clap(alias = "ls")]
+    List(list::ListCommand),
+
+    /// Remove mirrors that have not been used recently
+    Prune(prune::PruneCommand),
+
+    /// Show the total size of the cache
+    Size(size::SizeCommand),
+}
+
+#[cfg(feature = "cli")]
+impl CliCommand for CacheCommand {
+    fn command(
+        self,
+        config: crate::config::AppConfig,
+        dry_run: bool,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let cmd = self.command.unwrap_or(CacheCommands::List(list::ListCommand {}));
+
+        match cmd {
+            CacheCommands::List(a) => a.command(config, dry_run),
+            CacheCommands::Prune(a) => a.command(config, dry_run),
+            CacheCommands::Size(a) => a.command(config, dry_run),
+        }
+    }
+}
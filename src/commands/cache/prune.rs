@@ -0,0 +1,76 @@
+#[cfg(feature = "cli")]
+use std::time::Duration;
+
+#[cfg(feature = "cli")]
+#[derive(Debug, clap::Args)]
+pub struct PruneCommand {
+    /// remove mirrors not used in this many days
+    #[clap(long, default_value_t = 30)]
+    older_than: u64,
+
+    /// remove every mirror
+    #[clap(long)]
+    all: bool,
+
+    // global flags
+    #[clap(from_global)]
+    yes: bool,
+}
+
+#[cfg(feature = "cli")]
+impl crate::commands::CliCommand for PruneCommand {
+    fn command(
+        self,
+        config: crate::config::AppConfig,
+        dry_run: bool,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let args = self;
+        crate::trace!("Cache:Prune");
+        let cache = crate::cache::Cache::from_config(&config);
+        let max_age = Duration::from_secs(args.older_than * 86400);
+        let stale: Vec<_> = cache
+            .mirrors()
+            .into_iter()
+            .filter(|m| {
+                args.all
+                    || m.last_used
+                        .and_then(|t| t.elapsed().ok())
+                        .map(|age| age > max_age)
+                        .unwrap_or(true)
+            })
+            .collect();
+
+        if stale.is_empty() {
+            crate::info!("Nothing to prune");
+            return Ok(());
+        }
+
+        let size: u64 = stale.iter().map(|m| m.size).sum();
+        for mirror in &stale {
+            crate::info!("{} ({})", mirror.url, crate::cache::format_size(mirror.size));
+        }
+
+        if dry_run {
+            crate::info!("Dry Run, not removing {} mirrors", stale.len());
+            return Ok(());
+        }
+
+        if args.yes || dialoguer::Confirm::new()
+            .with_prompt(format!(
+                "You are about to remove {} mirrors ({})",
+                stale.len(),
+                crate::cache::format_size(size)
+            ))
+            .interact()
+            .unwrap_or(false)
+        {
+            for mirror in &stale {
+                cache.remove(mirror)?;
+            }
+            crate::info!("Removed {} mirrors, freed {}", stale.len(), crate::cache::format_size(size));
+        } else {
+            crate::info!("Not removing mirrors");
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,25 @@
+#[cfg(feature = "cli")]
+#[derive(Debug, clap::Args)]
+pub struct SizeCommand {}
+
+#[cfg(feature = "cli")]
+impl crate::commands::CliCommand for SizeCommand {
+    fn command(
+        self,
+        config: crate::config::AppConfig,
+        _dry_run: bool,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        crate::trace!("Cache:Size");
+        let cache = crate::cache::Cache::from_config(&config);
+        let mirrors = cache.mirrors();
+        let size: u64 = mirrors.iter().map(|m| m.size).sum();
+        println!(
+            "{} in {} mirrors ({})",
+            crate::cache::format_size(size),
+            mirrors.len(),
+            cache.mirrors_dir().display()
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,95 @@
+#[cfg(feature = "cli")]
+use {
+    crate::{
+        commands::{clone, CliCommand},
+        config::AppConfig,
+        repos,
+        update::{self, Outcome},
+    },
+    std::path::{Path, PathBuf},
+};
+
+#[cfg(feature = "cli")]
+#[derive(Debug, clap::Args)]
+pub struct FetchCommand {
+    /// Only fetch repos whose URL matches this pattern, eg. "github.com/acme/*"
+    filter: Option<String>,
+
+    /// number of repos fetched in parallel
+    #[clap(short, long, default_value_t = 8)]
+    jobs: usize,
+}
+
+#[cfg(feature = "cli")]
+impl CliCommand for FetchCommand {
+    fn command(self, config: AppConfig, dry_run: bool) -> Result<(), Box<dyn std::error::Error>> {
+        crate::trace!("Fetch");
+        update_all(&config, self.filter.as_deref(), self.jobs, dry_run, "fetch", update::fetch)
+    }
+}
+
+/// Run `f` on every managed repo matching the filter in parallel,
+/// then print a summary of the outcomes
+#[cfg(feature = "cli")]
+pub(crate) fn update_all(
+    config: &AppConfig,
+    filter: Option<&str>,
+    jobs: usize,
+    dry_run: bool,
+    operation: &str,
+    f: fn(&Path, &str) -> Outcome,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let targets: Vec<(PathBuf, String)> = repos::all_repos(config)
+        .into_iter()
+        .filter_map(|path| {
+            let origin = repos::origin_url(&path);
+            if origin.is_none() {
+                crate::debug!("skipping {}, it has no origin", path.display());
+            }
+            origin.map(|origin| (path, origin))
+        })
+        .filter(|(_, origin)| filter.is_none_or(|pattern| repos::matches_pattern(pattern, origin)))
+        .collect();
+
+    if dry_run {
+        for (path, origin) in &targets {
+            crate::info!("dry run: {} {} in {}", operation, origin, path.display());
+        }
+        return Ok(());
+    }
+
+    clone::install_interrupt_handler();
+    let outcomes = update::run_parallel(&targets, jobs, f);
+
+    let (mut updated, mut up_to_date, mut diverged, mut skipped, mut failed) = (0, 0, 0, 0, 0);
+    for ((path, _), outcome) in targets.iter().zip(&outcomes) {
+        match outcome {
+            Outcome::Updated => {
+                updated += 1;
+                crate::info!("updated {}", path.display());
+            }
+            Outcome::UpToDate => up_to_date += 1,
+            Outcome::Diverged => {
+                diverged += 1;
+                crate::warn!("{} has diverged from its upstream", path.display());
+            }
+            Outcome::Skipped(reason) => {
+                skipped += 1;
+                crate::warn!("skipped {}: {}", path.display(), reason);
+            }
+            Outcome::Failed(e) => {
+                failed += 1;
+                crate::error!("failed to {} {}: {}", operation, path.display(), e);
+            }
+        }
+    }
+    println!(
+        "{} updated, {} up to date, {} diverged, {} skipped, {} failed",
+        updated, up_to_date, diverged, skipped, failed
+    );
+
+    if failed > 0 {
+        return Err(format!("{} of {} repos failed to {}", failed, targets.len(), operation).into());
+    }
+    Ok(())
+}
@@ -0,0 +1,54 @@
+#[cfg(feature = "cli")]
+use {
+    crate::{commands::CliCommand, config::AppConfig, registry::Registry},
+    dialoguer::{console::Term, FuzzySelect},
+};
+
+#[cfg(feature = "cli")]
+#[derive(Debug, clap::Args)]
+pub struct FindCommand {
+    /// Words matched against the host, owner and name of the cloned repos
+    #[clap(required = true)]
+    query: Vec<String>,
+
+    // global flags
+    #[clap(from_global)]
+    yes: bool,
+}
+
+#[cfg(feature = "cli")]
+impl CliCommand for FindCommand {
+    fn command(self, _config: AppConfig, _dry_run: bool) -> Result<(), Box<dyn std::error::Error>> {
+        crate::trace!("Find");
+        let query = self.query.join(" ");
+        let registry = Registry::load()?;
+        let matches = registry.search(&query);
+        let entry = match matches.len() {
+            0 => return Err(format!("no cloned repo matches '{}', see `gclone list --scan`", query).into()),
+            1 => matches[0],
+            // the picker is drawn on stderr, stdout is usually captured by `gcd`
+            _ if self.yes || !Term::stderr().is_term() => matches[0],
+            _ => {
+                let items: Vec<String> = matches
+                    .iter()
+                    .map(|entry| format!("{} ({})", entry.url, entry.path))
+                    .collect();
+                let selection = FuzzySelect::new()
+                    .with_prompt("Repo")
+                    .items(&items)
+                    .default(0)
+                    .interact_on_opt(&Term::stderr())?;
+                match selection {
+                    Some(idx) => matches[idx],
+                    None => return Err("no repo selected".into()),
+                }
+            }
+        };
+        let path = entry.path.clone();
+        if let Err(e) = Registry::update(|registry| registry.visit(&path)) {
+            crate::warn!("unable to record the visit of {}: {}", &path, e);
+        }
+        println!("{}", path);
+        Ok(())
+    }
+}
@@ -0,0 +1,66 @@
+#[cfg(feature = "cli")]
+#[derive(Debug, clap::Args)]
+pub struct ApplyCommand {}
+
+#[cfg(feature = "cli")]
+impl crate::commands::CliCommand for ApplyCommand {
+    fn command(
+        self,
+        config: crate::config::AppConfig,
+        dry_run: bool,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        use crate::{commands::clone, registry::Registry, repos};
+
+        crate::trace!("Identity:Apply");
+        let registry = Registry::load()?;
+        let mut applied = 0;
+        let mut failed = 0;
+        for path in repos::all_repos(&config) {
+            let origin = match repos::origin_url(&path) {
+                Some(origin) => origin,
+                None => continue,
+            };
+            let meta = match clone::get_repo_meta(&origin, &clone::get_repo_type(&origin)) {
+                Ok(meta) => meta,
+                Err(e) => {
+                    crate::warn!("skipping {}, {}", path.display(), e);
+                    continue;
+                }
+            };
+            let template = registry
+                .find_by_path(&path)
+                .and_then(|entry| entry.template.clone())
+                .or_else(|| repos::template_of(&config, &path))
+                .unwrap_or_else(|| config.default_template.clone());
+            let entries = config.git_config_for(&meta.host, &meta.owner, &template);
+            if entries.is_empty() {
+                continue;
+            }
+            for (key, value) in &entries {
+                if dry_run {
+                    crate::info!("dry run: setting {} = {} in {}", key, value, path.display());
+                } else {
+                    crate::info!("setting {} = {} in {}", key, value, path.display());
+                }
+            }
+            if dry_run {
+                continue;
+            }
+            match repos::apply_git_config(&path, &entries) {
+                Ok(()) => applied += 1,
+                Err(e) => {
+                    crate::error!("unable to set the git config of {}: {}", path.display(), e.message());
+                    failed += 1;
+                }
+            }
+        }
+
+        if failed > 0 {
+            return Err(format!("{} repos failed to update", failed).into());
+        }
+        if !dry_run {
+            crate::info!("Updated the git config of {} repos", applied);
+        }
+        Ok(())
+    }
+}
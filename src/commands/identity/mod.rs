@@ -0,0 +1,31 @@
+#[cfg(feature = "cli")]
+use super::CliCommand;
+
+mod apply;
+
+#[cfg(feature = "cli")]
+#[derive(Debug, clap::Args)]
+pub struct IdentityCommand {
+    #[clap(subcommand)]
+    command: IdentityCommands,
+}
+
+#[cfg(feature = "cli")]
+#[derive(Debug, clap::Subcommand)]
+enum IdentityCommands {
+    /// Apply the git config of the matching rules to every cloned repo
+    Apply(apply::ApplyCommand),
+}
+
+#[cfg(feature = "cli")]
+impl CliCommand for IdentityCommand {
+    fn command(
+        self,
+        config: crate::config::AppConfig,
+        dry_run: bool,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        match self.command {
+            IdentityCommands::Apply(a) => a.command(config, dry_run),
+        }
+    }
+}
@@ -0,0 +1,62 @@
+#[cfg(feature = "cli")]
+use crate::{
+    commands::CliCommand,
+    config::AppConfig,
+    registry::{self, Registry},
+};
+
+#[cfg(feature = "cli")]
+#[derive(Debug, clap::Args)]
+pub struct ListCommand {
+    /// only list repos on this host, eg. `github.com`
+    #[clap(long)]
+    host: Option<String>,
+
+    /// only list repos of this owner
+    #[clap(long)]
+    owner: Option<String>,
+
+    /// only list repos cloned with this template
+    #[clap(short, long)]
+    template: Option<String>,
+
+    /// rebuild the registry from the repos found under the template roots
+    #[clap(long)]
+    scan: bool,
+}
+
+#[cfg(feature = "cli")]
+impl CliCommand for ListCommand {
+    fn command(self, config: AppConfig, dry_run: bool) -> Result<(), Box<dyn std::error::Error>> {
+        crate::trace!("List");
+        let mut registry = Registry::load()?;
+        if self.scan {
+            if dry_run {
+                crate::info!("dry run: not saving the registry");
+                registry.scan(&config);
+            } else {
+                Registry::update(|registry| registry.scan(&config))?;
+                registry = Registry::load()?;
+            }
+        }
+
+        let mut table = prettytable::Table::new();
+        table.add_row(row!["URL", "Path", "Template", "Branch", "Cloned"]);
+        for entry in registry.repos.iter().filter(|entry| {
+            self.host.as_ref().is_none_or(|host| entry.host.eq_ignore_ascii_case(host))
+                && self.owner.as_ref().is_none_or(|owner| entry.owner.eq_ignore_ascii_case(owner))
+                && self.template.as_ref().is_none_or(|template| entry.template.as_ref() == Some(template))
+        }) {
+            table.add_row(row![
+                entry.url,
+                entry.path,
+                entry.template.as_deref().unwrap_or("-"),
+                entry.branch.as_deref().unwrap_or("-"),
+                registry::format_timestamp(entry.cloned_at)
+            ]);
+        }
+        table.printstd();
+
+        Ok(())
+    }
+}
@@ -0,0 +1,43 @@
+#[cfg(feature = "cli")]
+use {
+    crate::{
+        commands::CliCommand,
+        config::AppConfig,
+        workspace::{self, LockedRepo, Lockfile, Manifest},
+    },
+    std::path::Path,
+};
+
+#[cfg(feature = "cli")]
+#[derive(Debug, clap::Args)]
+pub struct LockCommand {
+    /// Path to the workspace manifest
+    #[clap(default_value = workspace::MANIFEST_FILE)]
+    manifest: String,
+}
+
+#[cfg(feature = "cli")]
+impl CliCommand for LockCommand {
+    fn command(self, config: AppConfig, dry_run: bool) -> Result<(), Box<dyn std::error::Error>> {
+        crate::trace!("Lock");
+        let manifest_path = Path::new(&self.manifest);
+        let manifest = Manifest::load(manifest_path)?;
+
+        let mut lockfile = Lockfile::default();
+        for repo in &manifest.repos {
+            let options = manifest.clone_options(repo, &config.default_template)?;
+            let locked = LockedRepo::from_head(&options.repo_path, &options.target_path)?;
+            crate::info!("locked {} at {}", &locked.url, &locked.commit);
+            lockfile.repos.push(locked);
+        }
+
+        let lockfile_path = workspace::lockfile_path(manifest_path);
+        if dry_run {
+            crate::info!("dry run: not writing {}", lockfile_path.display());
+            return Ok(());
+        }
+        lockfile.save(&lockfile_path)?;
+        crate::info!("wrote {}", lockfile_path.display());
+        Ok(())
+    }
+}
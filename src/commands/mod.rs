@@ -1,5 +1,22 @@
 pub mod templates;
 pub mod clone;
+pub mod sync;
+pub mod lock;
+pub mod cache;
+pub mod bundle;
+pub mod unbundle;
+pub mod list;
+pub mod path;
+pub mod find;
+pub mod shell_init;
+pub mod status;
+pub mod fetch;
+pub mod pull;
+pub mod adopt;
+pub mod relocate;
+pub mod remove;
+pub mod identity;
+pub mod remote;
 
 #[cfg(feature = "cli")]
 pub trait CliCommand {
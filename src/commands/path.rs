@@ -0,0 +1,47 @@
+#[cfg(feature = "cli")]
+use {
+    crate::{
+        commands::{clone, CliCommand},
+        config::{self, AppConfig},
+    },
+    std::path::PathBuf,
+};
+
+/// Exit code when the target path does not exist on disk
+pub const NOT_FOUND_EXIT_CODE: i32 = 3;
+
+#[cfg(feature = "cli")]
+#[derive(Debug, clap::Args)]
+pub struct PathCommand {
+    /// The Git repository, in any form accepted by `gclone clone`
+    repo: String,
+
+    /// The template path the be used
+    #[clap(long, short, default_value_t = config::get_config().default_template)]
+    template: String,
+}
+
+#[cfg(feature = "cli")]
+impl CliCommand for PathCommand {
+    fn command(self, config: AppConfig, _dry_run: bool) -> Result<(), Box<dyn std::error::Error>> {
+        crate::trace!("Path");
+        // like `gclone clone`, a browser URL gives the path of the repo it points into
+        let repo = clone::parse_web_url(&self.repo).map(|(url, _)| url).unwrap_or(self.repo);
+        let repo_type = clone::get_repo_type(&repo);
+        let repo_meta = clone::get_repo_meta(&repo, &repo_type)?;
+        let target_path = PathBuf::from(clone::build_target_path(&config.get_template(&self.template), &repo_meta));
+        let target_path = if target_path.is_absolute() {
+            target_path
+        } else {
+            std::env::current_dir()?.join(target_path)
+        };
+        let exists = target_path.exists();
+        let target_path = if exists { target_path.canonicalize()? } else { target_path };
+
+        println!("{}", target_path.display());
+        if !exists {
+            std::process::exit(NOT_FOUND_EXIT_CODE);
+        }
+        Ok(())
+    }
+}
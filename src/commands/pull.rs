@@ -0,0 +1,25 @@
+#[cfg(feature = "cli")]
+use crate::{
+    commands::{fetch, CliCommand},
+    config::AppConfig,
+    update,
+};
+
+#[cfg(feature = "cli")]
+#[derive(Debug, clap::Args)]
+pub struct PullCommand {
+    /// Only pull repos whose URL matches this pattern, eg. "github.com/acme/*"
+    filter: Option<String>,
+
+    /// number of repos pulled in parallel
+    #[clap(short, long, default_value_t = 8)]
+    jobs: usize,
+}
+
+#[cfg(feature = "cli")]
+impl CliCommand for PullCommand {
+    fn command(self, config: AppConfig, dry_run: bool) -> Result<(), Box<dyn std::error::Error>> {
+        crate::trace!("Pull");
+        fetch::update_all(&config, self.filter.as_deref(), self.jobs, dry_run, "pull", update::pull)
+    }
+}
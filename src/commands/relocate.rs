@@ -0,0 +1,202 @@
+#[cfg(feature = "cli")]
+use {
+    crate::{
+        commands::{clone::{self, RepoMeta}, CliCommand},
+        config::{self, AppConfig},
+        registry::{Registry, RegistryEntry},
+        repos,
+    },
+    std::{
+        collections::BTreeSet,
+        path::{Path, PathBuf},
+    },
+};
+
+#[cfg(feature = "cli")]
+#[derive(Debug, clap::Args)]
+pub struct RelocateCommand {
+    /// The template the repos are currently laid out with
+    #[clap(long)]
+    from_template: String,
+
+    /// The template to move the repos to
+    #[clap(long, default_value_t = config::get_config().default_template)]
+    to_template: String,
+
+    // global flags
+    #[clap(from_global)]
+    yes: bool,
+}
+
+/// A planned move of a repo from one template to another
+#[cfg(feature = "cli")]
+struct Move {
+    path: PathBuf,
+    target: PathBuf,
+    /// root of the template the repo is moved out of
+    root: PathBuf,
+}
+
+#[cfg(feature = "cli")]
+impl CliCommand for RelocateCommand {
+    fn command(self, config: AppConfig, dry_run: bool) -> Result<(), Box<dyn std::error::Error>> {
+        crate::trace!("Relocate");
+        let to = config.get_template(&self.to_template);
+        let from = config.get_template(&self.from_template);
+
+        let mut candidates = repos::all_repos(&config);
+        candidates.extend(repos::find_repos(&repos::template_root(&from)));
+        let mut candidates: Vec<PathBuf> = candidates
+            .into_iter()
+            .map(|path| path.canonicalize().unwrap_or(path))
+            .collect();
+        candidates.sort();
+        candidates.dedup();
+
+        let cwd = std::env::current_dir()?;
+        let mut plan: Vec<Move> = Vec::new();
+        for path in candidates {
+            let values = match repos::match_template(&from, &path) {
+                Some(values) => values,
+                None => continue,
+            };
+            let origin = repos::origin_url(&path);
+            // the placeholders of the old template fill in what can't be taken from the origin
+            let meta = origin.as_deref().map(|origin| clone::get_repo_meta(origin, &clone::get_repo_type(origin)));
+            let mut meta = match meta {
+                Some(Ok(meta)) => meta,
+                _ => RepoMeta {
+                    repo: String::new(),
+                    owner: String::new(),
+                    provider: String::new(),
+                    host: String::new(),
+                },
+            };
+            for (key, value) in values {
+                match key.as_str() {
+                    "repo" => meta.repo = value,
+                    "owner" => meta.owner = value,
+                    "provider" => meta.provider = value,
+                    "host" => meta.host = value,
+                    _ => {}
+                }
+            }
+            if let Some(key) = missing_placeholder(&to, &meta) {
+                crate::warn!("skipping {}, its {} is unknown", path.display(), key);
+                continue;
+            }
+            let target = cwd.join(clone::build_target_path(&to, &meta));
+            if target == path {
+                continue;
+            }
+            plan.push(Move {
+                path,
+                target,
+                root: repos::template_root(&from),
+            });
+        }
+
+        if plan.is_empty() {
+            crate::info!("Nothing to relocate");
+            return Ok(());
+        }
+        for m in &plan {
+            println!("{} -> {}", m.path.display(), m.target.display());
+        }
+
+        // refuse the whole plan on any collision, rather than leaving a half-moved layout
+        let mut targets = BTreeSet::new();
+        let mut collisions = 0;
+        for m in &plan {
+            if m.target.exists() {
+                crate::error!("{} already exists", m.target.display());
+                collisions += 1;
+            } else if !targets.insert(&m.target) {
+                crate::error!("more than one repo would be moved to {}", m.target.display());
+                collisions += 1;
+            }
+        }
+        if collisions > 0 {
+            return Err(format!("{} collisions, not relocating any repos", collisions).into());
+        }
+
+        if dry_run {
+            crate::info!("Dry Run, not moving {} repos", plan.len());
+            return Ok(());
+        }
+        if !self.yes
+            && !dialoguer::Confirm::new()
+                .with_prompt(format!("You are about to move {} repos", plan.len()))
+                .interact()
+                .unwrap_or(false)
+        {
+            crate::info!("Not moving repos");
+            return Ok(());
+        }
+
+        for (idx, m) in plan.iter().enumerate() {
+            if let Err(e) = repos::move_repo(&m.path, &m.target) {
+                crate::error!("unable to move {} to {}: {}", m.path.display(), m.target.display(), e);
+                rollback(&plan[..idx], &repos::template_root(&to));
+                return Err(format!("relocation failed, {} moved repos were moved back", idx).into());
+            }
+        }
+        for m in &plan {
+            repos::prune_empty_parents(&m.path, &m.root);
+        }
+
+        Registry::update(|registry| {
+            for m in &plan {
+                let path = m.path.to_string_lossy().to_string();
+                let target = m.target.to_string_lossy().to_string();
+                match registry.repos.iter_mut().find(|entry| entry.path == path) {
+                    Some(entry) => {
+                        entry.path = target;
+                        entry.template = Some(self.to_template.clone());
+                    }
+                    None => {
+                        let entry = repos::origin_url(&m.target)
+                            .and_then(|origin| RegistryEntry::new(&origin, &target, Some(self.to_template.clone()), None).ok());
+                        if let Some(entry) = entry {
+                            registry.record(entry);
+                        }
+                    }
+                }
+            }
+            registry.repos.sort_by(|a, b| a.path.cmp(&b.path));
+        })?;
+
+        crate::info!("Relocated {} repos", plan.len());
+        Ok(())
+    }
+}
+
+/// Move already relocated repos back to where they were
+#[cfg(feature = "cli")]
+fn rollback(moved: &[Move], root: &Path) {
+    for m in moved.iter().rev() {
+        match repos::move_repo(&m.target, &m.path) {
+            Ok(()) => repos::prune_empty_parents(&m.target, root),
+            Err(e) => {
+                crate::error!("unable to move {} back to {}: {}", m.target.display(), m.path.display(), e);
+            }
+        }
+    }
+}
+
+/// Find a placeholder of the template that has no value
+#[cfg(feature = "cli")]
+fn missing_placeholder(template: &str, meta: &RepoMeta) -> Option<String> {
+    let re = regex::Regex::new(r"\{(.*?)\}").unwrap();
+    let missing = re
+        .captures_iter(template)
+        .map(|cap| cap[1].to_string())
+        .find(|key| match key.as_str() {
+            "repo" => meta.repo.is_empty(),
+            "owner" => meta.owner.is_empty(),
+            "provider" => meta.provider.is_empty(),
+            "host" => meta.host.is_empty(),
+            _ => false,
+        });
+    missing
+}
@@ -0,0 +1,100 @@
+#[cfg(feature = "cli")]
+#[derive(Debug, Clone, clap::ArgEnum)]
+pub enum Protocol {
+    Ssh,
+    Https,
+}
+
+#[cfg(feature = "cli")]
+#[derive(Debug, clap::Args)]
+pub struct ConvertCommand {
+    /// The protocol to convert the remotes to
+    #[clap(long, arg_enum)]
+    to: Protocol,
+
+    /// Only convert repos whose URL matches this pattern, eg. "github.com/acme/*"
+    filter: Option<String>,
+}
+
+#[cfg(feature = "cli")]
+impl crate::commands::CliCommand for ConvertCommand {
+    fn command(
+        self,
+        config: crate::config::AppConfig,
+        dry_run: bool,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        use crate::{commands::clone, repos};
+        use std::collections::BTreeSet;
+
+        crate::trace!("Remote:Convert");
+        let to_ssh = matches!(self.to, Protocol::Ssh);
+        let repos: Vec<(std::path::PathBuf, String)> = repos::all_repos(&config)
+            .into_iter()
+            .filter_map(|path| repos::origin_url(&path).map(|origin| (path, origin)))
+            .collect();
+        // other remotes are only converted if they are on a host one of the repos is cloned from
+        let known_hosts: BTreeSet<String> = repos
+            .iter()
+            .filter_map(|(_, origin)| clone::canonical_url(origin).split('/').next().map(String::from))
+            .collect();
+
+        let mut converted = 0;
+        let mut skipped = Vec::new();
+        for (path, origin) in &repos {
+            if !self.filter.as_ref().is_none_or(|pattern| repos::matches_pattern(pattern, origin)) {
+                continue;
+            }
+            let (repo, config) = match git2::Repository::open(path).and_then(|repo| repo.config().map(|config| (repo, config))) {
+                Ok(opened) => opened,
+                Err(e) => {
+                    crate::warn!("skipping {}, {}", path.display(), e.message());
+                    continue;
+                }
+            };
+            let main_remote = repos::main_remote(&repo);
+            for name in repo.remotes()?.iter().flatten() {
+                for push in [false, true] {
+                    let key = if push { "pushurl" } else { "url" };
+                    let url = match config.get_string(&format!("remote.{}.{}", name, key)) {
+                        Ok(url) => url,
+                        Err(_) => continue,
+                    };
+                    let label = if push { format!("{} (push)", name) } else { name.to_string() };
+                    let host = clone::canonical_url(&url).split('/').next().unwrap_or_default().to_string();
+                    if main_remote.as_deref() != Some(name) && !known_hosts.contains(&host) {
+                        skipped.push(format!("{} {} {}: not on a host any repo is cloned from", path.display(), label, url));
+                        continue;
+                    }
+                    let new_url = match clone::convert_url(&url, to_ssh) {
+                        Some(new_url) => new_url,
+                        // already in the requested form
+                        None if clone::convert_url(&url, !to_ssh).is_some() => continue,
+                        None => {
+                            skipped.push(format!("{} {} {}: not an HTTPS or SSH URL of a repo", path.display(), label, url));
+                            continue;
+                        }
+                    };
+                    println!("{} {}\n  - {}\n  + {}", path.display(), label, url, new_url);
+                    if !dry_run {
+                        if push {
+                            repo.remote_set_pushurl(name, Some(&new_url))?;
+                        } else {
+                            repo.remote_set_url(name, &new_url)?;
+                        }
+                    }
+                    converted += 1;
+                }
+            }
+        }
+
+        for remote in &skipped {
+            crate::warn!("skipped {}", remote);
+        }
+        if dry_run {
+            crate::info!("Dry Run, not converting {} remotes", converted);
+        } else {
+            crate::info!("Converted {} remotes", converted);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,31 @@
+#[cfg(feature = "cli")]
+use super::CliCommand;
+
+mod convert;
+
+#[cfg(feature = "cli")]
+#[derive(Debug, clap::Args)]
+pub struct RemoteCommand {
+    #[clap(subcommand)]
+    command: RemoteCommands,
+}
+
+#[cfg(feature = "cli")]
+#[derive(Debug, clap::Subcommand)]
+enum RemoteCommands {
+    /// Convert the remotes of cloned repos between HTTPS and SSH
+    Convert(convert::ConvertCommand),
+}
+
+#[cfg(feature = "cli")]
+impl CliCommand for RemoteCommand {
+    fn command(
+        self,
+        config: crate::config::AppConfig,
+        dry_run: bool,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        match self.command {
+            RemoteCommands::Convert(a) => a.command(config, dry_run),
+        }
+    }
+}
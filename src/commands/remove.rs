@@ -0,0 +1,148 @@
+#[cfg(feature = "cli")]
+use {
+    crate::{
+        commands::{clone, CliCommand},
+        config::AppConfig,
+        registry::Registry,
+        repos::{self, RepoStatus},
+    },
+    std::path::{Path, PathBuf},
+};
+
+#[cfg(feature = "cli")]
+#[derive(Debug, clap::Args)]
+pub struct RemoveCommand {
+    /// The repo to remove, by path, remote URL or a query matched like `find`
+    repo: String,
+
+    /// remove the repo even if it has work that only exists locally
+    #[clap(long)]
+    force: bool,
+
+    // global flags
+    #[clap(from_global)]
+    yes: bool,
+}
+
+#[cfg(feature = "cli")]
+impl CliCommand for RemoveCommand {
+    fn command(self, config: AppConfig, dry_run: bool) -> Result<(), Box<dyn std::error::Error>> {
+        crate::trace!("Remove");
+        let registry = Registry::load()?;
+        let path = resolve(&self.repo, &config, &registry)?;
+
+        let losses = local_work(&path)?;
+        if !losses.is_empty() {
+            for loss in &losses {
+                crate::warn!("{}: {}", path.display(), loss);
+            }
+            if !self.force {
+                return Err(format!(
+                    "{} has work that only exists locally and would be lost, use --force to remove it anyway",
+                    path.display()
+                )
+                .into());
+            }
+        }
+
+        if dry_run {
+            crate::info!("Dry Run, not removing {}", path.display());
+            return Ok(());
+        }
+        if !self.yes
+            && !dialoguer::Confirm::new()
+                .with_prompt(format!("You are about to remove {}", path.display()))
+                .interact()
+                .unwrap_or(false)
+        {
+            crate::info!("Not removing {}", path.display());
+            return Ok(());
+        }
+
+        let path_str = path.to_string_lossy().to_string();
+        let template = registry
+            .repos
+            .iter()
+            .find(|entry| entry.path == path_str)
+            .and_then(|entry| entry.template.clone());
+        std::fs::remove_dir_all(&path).map_err(|e| format!("unable to remove {}: {}", path.display(), e))?;
+        if let Some(root) = template_root(&config, template.as_deref(), &path) {
+            repos::prune_empty_parents(&path, &root);
+        }
+        Registry::update(|registry| registry.remove(&path_str))?;
+
+        crate::info!("Removed {}", path.display());
+        Ok(())
+    }
+}
+
+/// Find the repo to remove from a path, a remote URL or a query
+#[cfg(feature = "cli")]
+fn resolve(arg: &str, config: &AppConfig, registry: &Registry) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    let path = Path::new(arg);
+    if repos::is_repo(path) {
+        return Ok(path.canonicalize()?);
+    }
+
+    let url = clone::canonical_url(arg);
+    if let Some(entry) = registry
+        .repos
+        .iter()
+        .find(|entry| entry.url == url && repos::is_repo(Path::new(&entry.path)))
+    {
+        return Ok(PathBuf::from(&entry.path));
+    }
+    if arg.contains("://") || arg.contains('@') {
+        return repos::all_repos(config)
+            .into_iter()
+            .find(|path| repos::origin_url(path).is_some_and(|origin| clone::same_remote(&origin, arg)))
+            .ok_or_else(|| format!("no clone of {} found", arg).into());
+    }
+
+    let matches = registry.search(arg);
+    match matches.len() {
+        0 => Err(format!("no cloned repo matches '{}'", arg).into()),
+        1 => Ok(PathBuf::from(&matches[0].path)),
+        n => {
+            for entry in &matches {
+                crate::info!("{} ({})", entry.url, entry.path);
+            }
+            Err(format!("'{}' matches {} repos, give its path or URL instead", arg, n).into())
+        }
+    }
+}
+
+/// Describe the work in a repo that only exists locally
+#[cfg(feature = "cli")]
+fn local_work(path: &Path) -> Result<Vec<String>, git2::Error> {
+    let status = RepoStatus::new(path)?;
+    let mut losses = Vec::new();
+    if status.changed > 0 {
+        losses.push(format!("{} files with uncommitted changes", status.changed));
+    }
+    if status.untracked > 0 {
+        losses.push(format!("{} untracked files", status.untracked));
+    }
+    if status.stashes > 0 {
+        losses.push(format!("{} stashes", status.stashes));
+    }
+    for (branch, commits) in repos::unpushed_branches(path)? {
+        losses.push(format!("branch {} has {} commits that are not on any remote", branch, commits));
+    }
+    Ok(losses)
+}
+
+/// The root of the template the repo was cloned with,
+/// or else the deepest template root containing it
+#[cfg(feature = "cli")]
+fn template_root(config: &AppConfig, template: Option<&str>, path: &Path) -> Option<PathBuf> {
+    let mut roots: Vec<PathBuf> = match template {
+        Some(template) => vec![repos::template_root(&config.get_template(template))],
+        None => config.templates.values().map(|t| repos::template_root(t)).collect(),
+    };
+    roots = roots.into_iter().map(|root| root.canonicalize().unwrap_or(root)).collect();
+    roots
+        .into_iter()
+        .filter(|root| path.starts_with(root) && path != root)
+        .max_by_key(|root| root.components().count())
+}
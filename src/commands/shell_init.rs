@@ -0,0 +1,82 @@
+#[cfg(feature = "cli")]
+use crate::{commands::CliCommand, config::AppConfig};
+
+#[cfg(feature = "cli")]
+#[derive(Debug, Clone, clap::ArgEnum)]
+pub enum Shell {
+    Bash,
+    Zsh,
+    Fish,
+}
+
+#[cfg(feature = "cli")]
+#[derive(Debug, clap::Args)]
+pub struct ShellInitCommand {
+    #[clap(arg_enum)]
+    shell: Shell,
+}
+
+#[cfg(feature = "cli")]
+const POSIX_INIT: &str = r#"# gclone shell integration, add to your shell rc file:
+#   eval "$(gclone shell-init SHELL)"
+
+# cd into a cloned repo, eg. `gcd api`
+gcd() {
+    local dir
+    dir="$(command gclone find "$@")" && cd "$dir"
+}
+
+# cd into the new checkout after `gclone clone`
+gclone() {
+    # help and dry runs print to stdout and clone nothing to cd into
+    local arg
+    for arg in "$@"; do
+        case "$arg" in
+            -h|--help|-d|--dry-run) command gclone "$@"; return ;;
+        esac
+    done
+    if [ "$1" = "clone" ] || [ "$1" = "c" ]; then
+        local dir
+        dir="$(command gclone "$@" --print-path)" || return
+        [ -n "$dir" ] && cd "$dir"
+    else
+        command gclone "$@"
+    fi
+}
+"#;
+
+#[cfg(feature = "cli")]
+const FISH_INIT: &str = r#"# gclone shell integration, add to your config.fish:
+#   gclone shell-init fish | source
+
+# cd into a cloned repo, eg. `gcd api`
+function gcd
+    set -l dir (command gclone find $argv); and cd $dir
+end
+
+# cd into the new checkout after `gclone clone`
+function gclone
+    # help and dry runs print to stdout and clone nothing to cd into
+    if contains -- -h $argv; or contains -- --help $argv; or contains -- -d $argv; or contains -- --dry-run $argv
+        command gclone $argv
+    else if test "$argv[1]" = clone -o "$argv[1]" = c
+        set -l dir (command gclone $argv --print-path); or return
+        test -n "$dir"; and cd $dir
+    else
+        command gclone $argv
+    end
+end
+"#;
+
+#[cfg(feature = "cli")]
+impl CliCommand for ShellInitCommand {
+    fn command(self, _config: AppConfig, _dry_run: bool) -> Result<(), Box<dyn std::error::Error>> {
+        crate::trace!("ShellInit");
+        match self.shell {
+            Shell::Bash => print!("{}", POSIX_INIT.replace("SHELL", "bash")),
+            Shell::Zsh => print!("{}", POSIX_INIT.replace("SHELL", "zsh")),
+            Shell::Fish => print!("{}", FISH_INIT),
+        }
+        Ok(())
+    }
+}
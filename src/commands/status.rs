@@ -0,0 +1,58 @@
+#[cfg(feature = "cli")]
+use crate::{
+    commands::CliCommand,
+    config::AppConfig,
+    repos::{self, RepoStatus},
+};
+
+#[cfg(feature = "cli")]
+#[derive(Debug, clap::Args)]
+pub struct StatusCommand {
+    /// only show repos with uncommitted changes, untracked files, unpushed commits or stashes
+    #[clap(long)]
+    only_dirty: bool,
+
+    /// print the status as JSON
+    #[clap(long)]
+    json: bool,
+}
+
+#[cfg(feature = "cli")]
+impl CliCommand for StatusCommand {
+    fn command(self, config: AppConfig, _dry_run: bool) -> Result<(), Box<dyn std::error::Error>> {
+        crate::trace!("Status");
+        let mut statuses = Vec::new();
+        for path in repos::all_repos(&config) {
+            match RepoStatus::new(&path) {
+                Ok(status) if self.only_dirty && !status.is_dirty() => {}
+                Ok(status) => statuses.push(status),
+                Err(e) => {
+                    crate::warn!("unable to get the status of {}: {}", path.display(), e.message());
+                }
+            }
+        }
+
+        if self.json {
+            println!("{}", serde_json::to_string_pretty(&statuses)?);
+            return Ok(());
+        }
+
+        let count = |n: Option<usize>| n.map(|n| n.to_string()).unwrap_or_else(|| "-".to_string());
+        let mut table = prettytable::Table::new();
+        table.add_row(row!["Path", "Branch", "Changed", "Untracked", "Ahead", "Behind", "Stashes"]);
+        for status in &statuses {
+            table.add_row(row![
+                status.path.display(),
+                status.branch.as_deref().unwrap_or("(detached)"),
+                status.changed,
+                status.untracked,
+                count(status.ahead),
+                count(status.behind),
+                status.stashes
+            ]);
+        }
+        table.printstd();
+
+        Ok(())
+    }
+}
@@ -0,0 +1,131 @@
+#[cfg(feature = "cli")]
+use {
+    crate::{
+        commands::{clone, CliCommand},
+        config::AppConfig,
+        repos,
+        workspace::{self, Lockfile, Manifest},
+    },
+    std::{
+        collections::BTreeSet,
+        path::{Path, PathBuf},
+        sync::atomic::Ordering,
+    },
+};
+
+#[cfg(feature = "cli")]
+#[derive(Debug, clap::Args)]
+pub struct SyncCommand {
+    /// Path to the workspace manifest
+    #[clap(default_value = workspace::MANIFEST_FILE)]
+    manifest: String,
+
+    /// check out the exact commits pinned in the lockfile
+    #[clap(long)]
+    locked: bool,
+}
+
+#[cfg(feature = "cli")]
+impl CliCommand for SyncCommand {
+    fn command(self, config: AppConfig, dry_run: bool) -> Result<(), Box<dyn std::error::Error>> {
+        crate::trace!("Sync");
+        let manifest_path = Path::new(&self.manifest);
+        let manifest = Manifest::load(manifest_path)?;
+        let lockfile = if self.locked {
+            Some(Lockfile::load(&workspace::lockfile_path(manifest_path))?)
+        } else {
+            None
+        };
+
+        if !dry_run {
+            clone::install_interrupt_handler();
+        }
+
+        let mut roots = BTreeSet::new();
+        let mut known = BTreeSet::new();
+        let mut failed = 0;
+        for repo in &manifest.repos {
+            if clone::INTERRUPTED.load(Ordering::SeqCst) {
+                return Err("sync interrupted".into());
+            }
+            let template = manifest.template_for(repo, &config.default_template);
+            roots.insert(repos::template_root(&config.get_template(&template)));
+
+            let mut options = manifest.clone_options(repo, &config.default_template)?;
+            let locked = match &lockfile {
+                Some(lockfile) => {
+                    let locked = lockfile.find(&options.repo_path).ok_or_else(|| {
+                        format!("{} is not in the lockfile, run `gclone lock` first", &options.repo_path)
+                    })?;
+                    // the pinned commit may not be at the tip of the branch
+                    let shallow = git2::Repository::open(&options.target_path)
+                        .map(|repo| repo.is_shallow())
+                        .unwrap_or(false);
+                    options.depth = if shallow { Some(clone::UNSHALLOW_DEPTH) } else { None };
+                    Some(locked)
+                }
+                None => None,
+            };
+            let target_path = PathBuf::from(&options.target_path);
+            known.insert(target_path.clone());
+
+            let exists = target_path.exists();
+            if dry_run {
+                if exists {
+                    crate::info!("dry run: fetching {} in {}", &options.repo_path, &options.target_path);
+                } else {
+                    crate::info!("dry run: cloning {} to {}", &options.repo_path, &options.target_path);
+                }
+                if let Some(locked) = locked {
+                    crate::info!("dry run: checking out {} in {}", &locked.commit, &options.target_path);
+                }
+                continue;
+            }
+
+            let result = if exists {
+                // never fetch into a checkout of some other repo
+                match repos::origin_url(&target_path) {
+                    Some(origin) if clone::same_remote(&origin, options.origin()) => {
+                        crate::info!("fetching {} in {}", &options.repo_path, &options.target_path);
+                        options.git_fetch()
+                    }
+                    Some(origin) => Err(format!(
+                        "{} already contains a clone of {}, not {}",
+                        &options.target_path, origin, options.origin()
+                    ).into()),
+                    None => Err(format!(
+                        "{} already exists and is not a clone of {}",
+                        &options.target_path, options.origin()
+                    ).into()),
+                }
+            } else {
+                crate::info!("cloning {} to {}", &options.repo_path, &options.target_path);
+                options.git_clone()
+            };
+            let result = match (result, locked) {
+                (Ok(()), Some(locked)) => {
+                    crate::info!("checking out {} in {}", &locked.commit, &options.target_path);
+                    locked.checkout(&options.target_path)
+                }
+                (result, _) => result,
+            };
+            if let Err(e) = result {
+                crate::error!("failed to sync {}: {}", &options.repo_path, e);
+                failed += 1;
+            }
+        }
+
+        for root in &roots {
+            for path in repos::find_repos(root) {
+                if !known.contains(&path) {
+                    crate::warn!("not in manifest: {}", path.display());
+                }
+            }
+        }
+
+        if failed > 0 {
+            return Err(format!("{} of {} repos failed to sync", failed, manifest.repos.len()).into());
+        }
+        Ok(())
+    }
+}
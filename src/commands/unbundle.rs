@@ -0,0 +1,80 @@
+#[cfg(feature = "cli")]
+use {
+    crate::{
+        bundle::{self, BundleIndex},
+        commands::{clone::{self, CloneOptions}, CliCommand},
+        config::{self, AppConfig},
+    },
+    std::{path::Path, sync::atomic::Ordering},
+};
+
+#[cfg(feature = "cli")]
+#[derive(Debug, clap::Args)]
+pub struct UnbundleCommand {
+    /// Directory of bundles written by `gclone bundle`
+    dir: String,
+
+    /// The template path the be used
+    #[clap(long, short, default_value_t = config::get_config().default_template)]
+    template: String,
+}
+
+#[cfg(feature = "cli")]
+impl CliCommand for UnbundleCommand {
+    fn command(self, _config: AppConfig, dry_run: bool) -> Result<(), Box<dyn std::error::Error>> {
+        crate::trace!("Unbundle");
+        let dir = Path::new(&self.dir);
+        let index = BundleIndex::load(&dir.join(bundle::INDEX_FILE))?;
+
+        if !dry_run {
+            clone::install_interrupt_handler();
+        }
+        let mut failed = 0;
+        for entry in &index.bundles {
+            if clone::INTERRUPTED.load(Ordering::SeqCst) {
+                return Err("unbundle interrupted".into());
+            }
+            let bundle_path = match dir.join(&entry.file).canonicalize() {
+                Ok(path) => path,
+                Err(e) => {
+                    crate::error!("failed to restore {}: {}", dir.join(&entry.file).display(), e);
+                    failed += 1;
+                    continue;
+                }
+            };
+            let mut options = CloneOptions::new(
+                bundle_path.to_string_lossy().to_string(),
+                &self.template,
+                None,
+                false,
+                None,
+                None,
+                None,
+            )?;
+            let placed_by_url = match &entry.url {
+                Some(url) => options.set_meta(None, None, Some(url.clone())).is_ok(),
+                None => false,
+            };
+            if !placed_by_url {
+                // the bundle's own owner and repo, for origins that can't be parsed
+                options.set_meta(Some(entry.owner.clone()), Some(entry.repo.clone()), None)?;
+                options.origin_url = entry.url.clone();
+            }
+
+            if dry_run {
+                crate::info!("dry run: restoring {} to {}", &entry.file, &options.target_path);
+                continue;
+            }
+            crate::info!("restoring {} to {}", &entry.file, &options.target_path);
+            if let Err(e) = options.git_clone() {
+                crate::error!("failed to restore {}: {}", &entry.file, e);
+                failed += 1;
+            }
+        }
+
+        if failed > 0 {
+            return Err(format!("{} of {} bundles failed to restore", failed, index.bundles.len()).into());
+        }
+        Ok(())
+    }
+}
@@ -1,11 +1,104 @@
-use std::collections::HashMap;
+use std::{collections::{BTreeMap, HashMap}, path::PathBuf};
 use serde::{Serialize, Deserialize};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct AppConfig {
   pub version: String,
   pub default_template: String,
-  pub templates: HashMap<String, String>
+  /// number of times a clone or fetch is retried after a transient network error
+  #[serde(default = "default_retries")]
+  pub retries: u32,
+  /// delay before the first retry, doubled on every following attempt
+  #[serde(default = "default_retry_backoff_ms")]
+  pub retry_backoff_ms: u64,
+  /// clone through the local object cache, see `gclone cache`
+  #[serde(default)]
+  pub use_cache: bool,
+  /// directory of the object cache, defaults to the user cache directory
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub cache_dir: Option<String>,
+  /// templates of local mirror directories to clone from when offline,
+  /// eg. `/mnt/mirrors/{host}/{owner}/{repo}.git`
+  #[serde(default)]
+  pub mirror_templates: Vec<String>,
+  /// commands run in every new checkout, see `Rule` for per template and host hooks
+  #[serde(default)]
+  pub post_clone: Vec<String>,
+  /// seconds a post-clone hook may run before it is killed
+  #[serde(default = "default_hook_timeout_secs")]
+  pub hook_timeout_secs: u64,
+  /// whether a fork cloned with `--upstream` is placed by the fork's or the upstream's path
+  #[serde(default)]
+  pub fork_layout: ForkLayout,
+  // tables must come after plain values in toml
+  pub templates: HashMap<String, String>,
+  #[serde(default, skip_serializing_if = "Vec::is_empty")]
+  pub rules: Vec<Rule>,
+}
+
+/// Where a fork cloned with `--upstream` is placed
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ForkLayout {
+  /// by the fork's owner and name, ie. `~/git/github/me/project`
+  #[default]
+  Fork,
+  /// by the upstream's owner and name, ie. `~/git/github/original/project`
+  Upstream,
+}
+
+/// Settings for the repos matching a host, owner and/or template,
+/// a rule without any of them matches every repo.
+///
+/// ```toml
+/// [[rules]]
+/// host = "gitlab.internal"
+/// post_clone = ["direnv allow"]
+///
+/// [[rules]]
+/// template = "work"
+/// post_clone = ["pre-commit install"]
+///
+/// [rules.remotes]
+/// mirror = "git@backup.internal:{owner}/{repo}.git"
+///
+/// [rules.git_config]
+/// "user.email" = "me@work.example"
+/// "commit.gpgsign" = true
+/// ```
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct Rule {
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub host: Option<String>,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub owner: Option<String>,
+  /// template name, or template string
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub template: Option<String>,
+  /// fetch over HTTPS but push over SSH, like `gclone clone --push-ssh`
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub push_ssh: Option<bool>,
+  /// commands run in new checkouts, after the global ones
+  #[serde(default, skip_serializing_if = "Vec::is_empty")]
+  pub post_clone: Vec<String>,
+  /// remotes added to new checkouts, by name, with URLs rendered like templates,
+  /// eg. `mirror = "git@backup.internal:{owner}/{repo}.git"`
+  #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+  pub remotes: BTreeMap<String, String>,
+  /// entries written into the local git config of new checkouts,
+  /// eg. `user.name`, `user.email`, `user.signingkey`, `commit.gpgsign` or `core.hooksPath`
+  #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+  pub git_config: BTreeMap<String, toml::Value>,
+}
+
+impl Rule {
+  /// Check if the rule applies to a repo on `host` of `owner`, cloned with `template`
+  /// (given by name or as a template string)
+  pub fn matches(&self, config: &AppConfig, host: &str, owner: &str, template: &str) -> bool {
+    self.host.as_ref().is_none_or(|h| h.eq_ignore_ascii_case(host))
+      && self.owner.as_ref().is_none_or(|o| o.eq_ignore_ascii_case(owner))
+      && self.template.as_ref().is_none_or(|t| config.get_template(t) == config.get_template(template))
+  }
 }
 
 impl std::default::Default for AppConfig {
@@ -13,7 +106,16 @@ impl std::default::Default for AppConfig {
     Self {
       version: "0.1.0".into(),
       default_template: "default".into(),
+      retries: default_retries(),
+      retry_backoff_ms: default_retry_backoff_ms(),
+      use_cache: false,
+      cache_dir: None,
+      mirror_templates: Vec::new(),
+      post_clone: Vec::new(),
+      hook_timeout_secs: default_hook_timeout_secs(),
+      fork_layout: ForkLayout::default(),
       templates: HashMap::from([("default".into(), get_default_template())]),
+      rules: Vec::new(),
     }
   }
 }
@@ -47,9 +149,42 @@ impl AppConfig {
   pub fn remove_template(&mut self, name: &str) {
     self.templates.remove(name);
   }
+
+  /// The rules that apply to a repo, in the order of the config
+  pub fn rules_for(&self, host: &str, owner: &str, template: &str) -> Vec<&Rule> {
+    self.rules.iter().filter(|rule| rule.matches(self, host, owner, template)).collect()
+  }
+
+  /// The git config entries of the rules that apply to a repo,
+  /// later rules override earlier ones
+  pub fn git_config_for(&self, host: &str, owner: &str, template: &str) -> BTreeMap<String, toml::Value> {
+    self.rules_for(host, owner, template)
+      .into_iter()
+      .flat_map(|rule| rule.git_config.clone())
+      .collect()
+  }
+
+  pub fn get_cache_dir(&self) -> PathBuf {
+    match &self.cache_dir {
+      Some(dir) => PathBuf::from(dir),
+      None => get_project_dirs().cache_dir().to_path_buf(),
+    }
+  }
     
 }
 
+fn default_retries() -> u32 {
+  2
+}
+
+fn default_retry_backoff_ms() -> u64 {
+  1000
+}
+
+fn default_hook_timeout_secs() -> u64 {
+  300
+}
+
 #[cfg(target_family = "unix")]
 fn get_default_template() -> String {
   "~/git/{provider}/{owner}/{repo}".into()
@@ -60,6 +195,17 @@ fn get_default_template() -> String {
   "C:\\git\\{provider}\\{owner}\\{repo}".into()
 }
 
+/// The user data directory, for the repo registry
+pub fn get_data_dir() -> PathBuf {
+  get_project_dirs().data_dir().to_path_buf()
+}
+
+/// The directories used by confy for the config,
+/// so the cache and data files live next to it.
+fn get_project_dirs() -> directories::ProjectDirs {
+  directories::ProjectDirs::from("rs", "", "global_clone").unwrap()
+}
+
 pub fn get_config() -> AppConfig {
   confy::load("global_clone").unwrap()
 }
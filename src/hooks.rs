@@ -0,0 +1,51 @@
+use std::{
+    path::Path,
+    process::{Command, Stdio},
+    sync::atomic::Ordering,
+    time::{Duration, Instant},
+};
+
+use crate::{commands::clone::INTERRUPTED, trace};
+
+/// Run a hook command with the shell in `dir`, killing it after `timeout`.
+///
+/// The hook's output goes to stderr, so it doesn't mix with paths printed for scripts.
+pub fn run_hook(command: &str, dir: &Path, env: &[(&str, &str)], timeout: Duration) -> Result<(), String> {
+    #[cfg(feature = "logging")]
+    trace!("run_hook: {} in {}", command, dir.display());
+    let mut cmd = if cfg!(target_family = "windows") {
+        let mut cmd = Command::new("cmd");
+        cmd.arg("/C").arg(command);
+        cmd
+    } else {
+        let mut cmd = Command::new("sh");
+        cmd.arg("-c").arg(command);
+        cmd
+    };
+    let mut child = cmd
+        .current_dir(dir)
+        .envs(env.iter().copied())
+        .stdin(Stdio::null())
+        .stdout(std::io::stderr())
+        .spawn()
+        .map_err(|e| format!("unable to run: {}", e))?;
+
+    let started = Instant::now();
+    loop {
+        match child.try_wait().map_err(|e| e.to_string())? {
+            Some(status) if status.success() => return Ok(()),
+            Some(status) => return Err(format!("exited with {}", status)),
+            None if INTERRUPTED.load(Ordering::SeqCst) => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err("interrupted".to_string());
+            }
+            None if started.elapsed() > timeout => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(format!("timed out after {}s", timeout.as_secs()));
+            }
+            None => std::thread::sleep(Duration::from_millis(50)),
+        }
+    }
+}
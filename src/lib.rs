@@ -1,6 +1,13 @@
 #[cfg(feature = "cli")]
 #[macro_use] extern crate prettytable;
 
+pub mod bundle;
+pub mod cache;
 pub mod config;
+pub mod hooks;
 pub mod commands;
-pub mod logging;
\ No newline at end of file
+pub mod logging;
+pub mod registry;
+pub mod repos;
+pub mod update;
+pub mod workspace;
@@ -2,7 +2,7 @@ use clap::{Parser, Subcommand, Command, IntoApp};
 use clap_verbosity_flag::{Verbosity, InfoLevel};
 
 use global_clone::{
-    commands::{templates, clone, CliCommand},
+    commands::{templates, clone, sync, lock, cache, bundle, unbundle, list, path, find, shell_init, status, fetch, pull, adopt, relocate, remove, identity, remote, CliCommand},
     config::{AppConfig, self},
 };
 
@@ -47,6 +47,8 @@ enum Commands {
     /// - `{owner}` - The owner of the repo (ex: github.com/owner)
     /// 
     /// - `{repo}` - The name of the repo (ex: repo)
+    ///
+    /// - `{host}` - The host of the Git Provider (ex: github.com)
     #[clap(alias = "t", about)]
     Templates(templates::TemplatesCommand),
     
@@ -57,7 +59,154 @@ enum Commands {
     /// 
     /// see: `templates` command for more information.
     #[clap(alias = "c", about)]
-    Clone(clone::CloneCommand),
+    Clone(Box<clone::CloneCommand>),
+
+    /// Sync a workspace
+    ///
+    /// Clone every repo listed in a workspace manifest that is missing,
+    /// and fetch the ones that already exist.
+    /// Repos found under the template roots that are not in the manifest are reported.
+    ///
+    /// The manifest is a TOML file with a list of `[[repos]]`, each with a `url`
+    /// and an optional `template`, `branch`, `depth` and `tags`.
+    ///
+    /// With `--locked` the commits pinned in the lockfile are checked out (detached).
+    #[clap(about)]
+    Sync(sync::SyncCommand),
+
+    /// Lock a workspace
+    ///
+    /// Record the remote URL, path and current HEAD commit of every repo
+    /// in a workspace manifest into a lockfile next to it (`workspace.lock`),
+    /// so the exact state can be recreated with `sync --locked`.
+    #[clap(about)]
+    Lock(lock::LockCommand),
+
+    /// Cache Commands - manage the local object cache
+    ///
+    /// When cloning with `--cache` (or `use_cache = true` in the config),
+    /// repos are first fetched into a bare mirror in the cache,
+    /// then cloned locally from it, so repeated clones reuse its objects.
+    #[clap(about)]
+    Cache(cache::CacheCommand),
+
+    /// Export repos as git bundles
+    ///
+    /// Write a bundle with all refs of each repo to `<out>/{provider}/{owner}/{repo}.bundle`,
+    /// and an `index.toml` recording the original remote URL of each bundle.
+    /// Repos are given by path, or by a pattern matched against the URLs of the repos
+    /// under the template roots, eg. "github.com/acme/*".
+    ///
+    /// see: `unbundle` to restore them.
+    #[clap(about)]
+    Bundle(bundle::BundleCommand),
+
+    /// Restore repos exported with `bundle`
+    ///
+    /// Clone every bundle in the index into its templated path,
+    /// with `origin` set to the original remote URL.
+    #[clap(about)]
+    Unbundle(unbundle::UnbundleCommand),
+
+    /// List cloned repos
+    ///
+    /// Every clone is recorded in a registry in the user data directory,
+    /// with its URL, path, template, branch and the time it was cloned.
+    /// Use `--scan` to rebuild the registry from the repos found under the template roots,
+    /// eg. for repos cloned before the registry existed.
+    #[clap(alias = "ls", about)]
+    List(list::ListCommand),
+
+    /// Print the target path of a repo
+    ///
+    /// Print the absolute path the repo is (or would be) cloned to, without cloning it,
+    /// eg. `cd "$(gclone path acme/api)"`.
+    /// Exits with code 3 if the path does not exist.
+    #[clap(about)]
+    Path(path::PathCommand),
+
+    /// Find a cloned repo
+    ///
+    /// Fuzzy-match the query against the host, owner and name of the repos in the registry
+    /// and print the path of the best match. When several repos match,
+    /// a picker is shown (unless `--yes` is given or there is no terminal).
+    ///
+    /// see: `shell-init` for the `gcd` shell function built on it.
+    #[clap(alias = "f", about)]
+    Find(find::FindCommand),
+
+    /// Print the shell integration
+    ///
+    /// Print a `gcd` function, to cd into a repo found with `find`,
+    /// and a `gclone` wrapper that cds into the new checkout after `gclone clone`.
+    ///
+    /// eg. `eval "$(gclone shell-init bash)"` in `~/.bashrc`.
+    #[clap(about)]
+    ShellInit(shell_init::ShellInitCommand),
+
+    /// Show the status of all cloned repos
+    ///
+    /// For every repo under the template roots or in the registry, show the current branch,
+    /// the number of changed and untracked files, the commits ahead and behind its upstream
+    /// and the number of stashes.
+    #[clap(alias = "st", about)]
+    Status(status::StatusCommand),
+
+    /// Fetch all cloned repos
+    ///
+    /// Fetch `origin` of every repo under the template roots or in the registry, in parallel,
+    /// pruning deleted branches. An optional pattern limits it to matching repos, eg. "github.com/acme/*".
+    #[clap(about)]
+    Fetch(fetch::FetchCommand),
+
+    /// Pull all cloned repos
+    ///
+    /// Fetch every repo and fast-forward its current branch, in parallel.
+    /// Repos with local changes, a detached HEAD or a diverged branch are left alone.
+    #[clap(about)]
+    Pull(pull::PullCommand),
+
+    /// Adopt existing repos into the templated layout
+    ///
+    /// Scan the given directories for repos, compute the templated path of each
+    /// from its `origin` URL, show the plan and move them there.
+    /// Repos without an `origin` are skipped, existing targets are never overwritten.
+    /// With `--link` a symlink to the new location is left behind.
+    #[clap(about)]
+    Adopt(adopt::AdoptCommand),
+
+    /// Move repos to a new template
+    ///
+    /// Find the repos laid out with the old template, compute their path with the new one
+    /// (the default template unless `--to-template` is given) and move them there.
+    /// Nothing is moved if any target already exists, and moves are rolled back if one fails.
+    #[clap(about)]
+    Relocate(relocate::RelocateCommand),
+
+    /// Remove a cloned repo
+    ///
+    /// Remove a repo, given by path, remote URL or a query matched like `find`,
+    /// along with the parent directories left empty up to the template root.
+    /// Repos with uncommitted changes, untracked files, stashes or commits that are
+    /// not on any remote are only removed with `--force`.
+    #[clap(alias = "rm", about)]
+    Remove(remove::RemoveCommand),
+
+    /// Identity Commands - manage the git config of cloned repos
+    ///
+    /// Config rules can set git config entries (eg. `user.email` or `commit.gpgsign`)
+    /// for the repos matching a host, owner and/or template. They are written into
+    /// the local config of new checkouts, `identity apply` writes them into existing ones.
+    #[clap(about)]
+    Identity(identity::IdentityCommand),
+
+    /// Remote Commands - manage the remotes of cloned repos
+    ///
+    /// `remote convert --to ssh|https [pattern]` rewrites `origin`, and the other remotes
+    /// on the hosts the repos are cloned from, between their HTTPS and SSH forms.
+    /// With `--dry-run` the old and new URLs are only shown.
+    #[clap(about)]
+    Remote(remote::RemoteCommand),
 
     /// Generate Shell completion Scripts
     /// 
@@ -97,6 +246,23 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     match cli_args.command {
         Commands::Templates(a) => a.command(cfg, cli_args.dry_run),
         Commands::Clone(a) => a.command(cfg, cli_args.dry_run),
+        Commands::Sync(a) => a.command(cfg, cli_args.dry_run),
+        Commands::Lock(a) => a.command(cfg, cli_args.dry_run),
+        Commands::Cache(a) => a.command(cfg, cli_args.dry_run),
+        Commands::Bundle(a) => a.command(cfg, cli_args.dry_run),
+        Commands::Unbundle(a) => a.command(cfg, cli_args.dry_run),
+        Commands::List(a) => a.command(cfg, cli_args.dry_run),
+        Commands::Path(a) => a.command(cfg, cli_args.dry_run),
+        Commands::Find(a) => a.command(cfg, cli_args.dry_run),
+        Commands::ShellInit(a) => a.command(cfg, cli_args.dry_run),
+        Commands::Status(a) => a.command(cfg, cli_args.dry_run),
+        Commands::Fetch(a) => a.command(cfg, cli_args.dry_run),
+        Commands::Pull(a) => a.command(cfg, cli_args.dry_run),
+        Commands::Adopt(a) => a.command(cfg, cli_args.dry_run),
+        Commands::Relocate(a) => a.command(cfg, cli_args.dry_run),
+        Commands::Remove(a) => a.command(cfg, cli_args.dry_run),
+        Commands::Identity(a) => a.command(cfg, cli_args.dry_run),
+        Commands::Remote(a) => a.command(cfg, cli_args.dry_run),
         Commands::ShellCompletion(a) => completion(a, cfg, cli_args.dry_run),
     }
 }
@@ -0,0 +1,307 @@
+use fuzzy_matcher::{skim::SkimMatcherV2, FuzzyMatcher};
+use serde::{Deserialize, Serialize};
+use std::{
+    path::{Path, PathBuf},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use crate::commands::clone::{build_target_path, canonical_url, get_repo_meta, get_repo_type};
+use crate::{config::AppConfig, repos, trace};
+
+/// The registry of every repo cloned (or found by `gclone list --scan`),
+/// stored in the user data directory.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Registry {
+    #[serde(default)]
+    pub repos: Vec<RegistryEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegistryEntry {
+    /// canonical URL, ie. `github.com/owner/repo`
+    pub url: String,
+    /// the `origin` URL the repo was cloned from
+    pub remote: String,
+    pub host: String,
+    pub owner: String,
+    pub repo: String,
+    /// resolved target path
+    pub path: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub template: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub branch: Option<String>,
+    /// unix timestamp of the clone
+    pub cloned_at: u64,
+    /// number of times the repo was cloned or visited with `gclone find`
+    #[serde(default)]
+    pub visits: u32,
+    /// unix timestamp of the last visit
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_visited: Option<u64>,
+}
+
+impl RegistryEntry {
+    pub fn new(remote: &str, path: &str, template: Option<String>, branch: Option<String>) -> Result<Self, Box<dyn std::error::Error>> {
+        let meta = get_repo_meta(remote, &get_repo_type(remote))?;
+        Ok(Self {
+            url: canonical_url(remote),
+            remote: remote.to_string(),
+            host: meta.host,
+            owner: meta.owner,
+            repo: meta.repo,
+            path: path.to_string(),
+            template,
+            branch,
+            cloned_at: now(),
+            visits: 0,
+            last_visited: None,
+        })
+    }
+
+    /// Frecency of the repo, its visits weighted by how recent the last one was,
+    /// like zoxide does
+    pub fn frecency(&self, now: u64) -> f64 {
+        let last_visited = match self.last_visited {
+            Some(last_visited) => last_visited,
+            None => return 0.0,
+        };
+        let age = now.saturating_sub(last_visited);
+        let weight = match age {
+            age if age < 3600 => 4.0,
+            age if age < 86400 => 2.0,
+            age if age < 7 * 86400 => 0.5,
+            _ => 0.25,
+        };
+        self.visits as f64 * weight
+    }
+}
+
+impl Registry {
+    pub fn path() -> PathBuf {
+        crate::config::get_data_dir().join("registry.toml")
+    }
+
+    /// Load the registry, an empty registry is returned if there is none yet
+    pub fn load() -> Result<Self, Box<dyn std::error::Error>> {
+        let path = Self::path();
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let contents = std::fs::read_to_string(&path)
+            .map_err(|e| format!("unable to read registry {}: {}", path.display(), e))?;
+        let registry = toml::from_str(&contents)
+            .map_err(|e| format!("invalid registry {}: {}", path.display(), e))?;
+        Ok(registry)
+    }
+
+    pub fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let path = Self::path();
+        #[cfg(feature = "logging")]
+        trace!("saving registry {}", path.display());
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&path, toml::to_string_pretty(self)?)
+            .map_err(|e| format!("unable to write registry {}: {}", path.display(), e))?;
+        Ok(())
+    }
+
+    /// Load the registry, apply `f` and save it again,
+    /// holding a lock so concurrent gclone processes don't drop each other's changes
+    pub fn update<T>(f: impl FnOnce(&mut Self) -> T) -> Result<T, Box<dyn std::error::Error>> {
+        let _lock = RegistryLock::acquire()?;
+        let mut registry = Self::load()?;
+        let result = f(&mut registry);
+        registry.save()?;
+        Ok(result)
+    }
+
+    /// Add a repo, replacing any entry with the same path,
+    /// and count it as a visit
+    pub fn record(&mut self, mut entry: RegistryEntry) {
+        if let Some(known) = self.repos.iter().find(|r| r.path == entry.path) {
+            entry.visits = known.visits;
+        }
+        entry.visits += 1;
+        entry.last_visited = Some(now());
+        self.repos.retain(|r| r.path != entry.path);
+        self.repos.push(entry);
+        self.repos.sort_by(|a, b| a.path.cmp(&b.path));
+    }
+
+    /// Remove the repo at `path`
+    pub fn remove(&mut self, path: &str) {
+        self.repos.retain(|r| r.path != path);
+    }
+
+    /// Count a visit of the repo at `path`, for frecency ranking
+    pub fn visit(&mut self, path: &str) {
+        if let Some(entry) = self.repos.iter_mut().find(|r| r.path == path) {
+            entry.visits += 1;
+            entry.last_visited = Some(now());
+        }
+    }
+
+    pub fn find_by_path(&self, path: &Path) -> Option<&RegistryEntry> {
+        self.repos.iter().find(|r| Path::new(&r.path) == path)
+    }
+
+    /// Fuzzy-match repos against a query, best match first.
+    ///
+    /// Every word of the query has to match the repo's `host/owner/repo`,
+    /// repos that no longer exist on disk are skipped.
+    /// Matches are ranked by their match score, boosted by their frecency.
+    pub fn search(&self, query: &str) -> Vec<&RegistryEntry> {
+        let matcher = SkimMatcherV2::default().ignore_case();
+        let now = now();
+        let mut matches: Vec<(f64, &RegistryEntry)> = self
+            .repos
+            .iter()
+            .filter(|entry| Path::new(&entry.path).exists())
+            .filter_map(|entry| {
+                query
+                    .split_whitespace()
+                    .map(|word| {
+                        let score = matcher.fuzzy_match(&entry.url, word)?;
+                        // prefer matches on the repo name itself
+                        Some(if entry.repo.eq_ignore_ascii_case(word) { score * 2 } else { score })
+                    })
+                    .sum::<Option<i64>>()
+                    .map(|score| (score as f64 * (1.0 + entry.frecency(now)), entry))
+            })
+            .collect();
+        matches.sort_by(|a, b| b.0.total_cmp(&a.0).then_with(|| a.1.path.cmp(&b.1.path)));
+        matches.into_iter().map(|(_, entry)| entry).collect()
+    }
+
+    /// Rebuild the registry from the repos found under the template roots,
+    /// keeping what is already known about repos that are still there.
+    pub fn scan(&mut self, config: &AppConfig) {
+        let mut repos = Vec::new();
+        for path in repos::managed_repos(config) {
+            let path = std::fs::canonicalize(&path).unwrap_or(path);
+            let remote = match repos::origin_url(&path) {
+                Some(remote) => remote,
+                None => continue,
+            };
+            if let Some(known) = self.find_by_path(&path) {
+                repos.push(known.clone());
+                continue;
+            }
+            let path_str = path.to_string_lossy().to_string();
+            let template = find_template(config, &remote, &path);
+            let branch = git2::Repository::open(&path)
+                .ok()
+                .and_then(|repo| repo.head().ok().and_then(|h| h.shorthand().map(String::from)));
+            let mut entry = match RegistryEntry::new(&remote, &path_str, template, branch) {
+                Ok(entry) => entry,
+                Err(e) => {
+                    crate::warn!("skipping {}, {}", path.display(), e);
+                    continue;
+                }
+            };
+            entry.cloned_at = std::fs::metadata(path.join(".git"))
+                .and_then(|m| m.modified())
+                .map(timestamp)
+                .unwrap_or(entry.cloned_at);
+            repos.push(entry);
+        }
+        repos.sort_by(|a, b| a.path.cmp(&b.path));
+        self.repos = repos;
+    }
+}
+
+/// How long to wait for another gclone to release the registry
+const LOCK_TIMEOUT: Duration = Duration::from_secs(10);
+/// Age after which a lock is assumed to be left behind by a killed gclone
+const LOCK_STALE: Duration = Duration::from_secs(60);
+
+/// A lock file next to the registry, held while it is read, modified and written back
+/// and removed when dropped
+struct RegistryLock {
+    path: PathBuf,
+}
+
+impl RegistryLock {
+    fn acquire() -> Result<Self, Box<dyn std::error::Error>> {
+        let path = Registry::path().with_extension("toml.lock");
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let start = Instant::now();
+        loop {
+            match std::fs::OpenOptions::new().write(true).create_new(true).open(&path) {
+                Ok(_) => return Ok(Self { path }),
+                Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {
+                    let stale = std::fs::metadata(&path)
+                        .and_then(|m| m.modified())
+                        .ok()
+                        .and_then(|modified| modified.elapsed().ok())
+                        .is_some_and(|age| age > LOCK_STALE);
+                    if stale {
+                        crate::warn!("removing stale registry lock {}", path.display());
+                        let _ = std::fs::remove_file(&path);
+                        continue;
+                    }
+                    if start.elapsed() > LOCK_TIMEOUT {
+                        return Err(format!(
+                            "the registry is locked by another gclone, remove {} if none is running",
+                            path.display()
+                        )
+                        .into());
+                    }
+                    std::thread::sleep(Duration::from_millis(20));
+                }
+                Err(e) => return Err(format!("unable to lock registry {}: {}", path.display(), e).into()),
+            }
+        }
+    }
+}
+
+impl Drop for RegistryLock {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+/// Find the name of the template that places the repo with this remote at `path`
+fn find_template(config: &AppConfig, remote: &str, path: &Path) -> Option<String> {
+    let meta = get_repo_meta(remote, &get_repo_type(remote)).ok()?;
+    let mut names: Vec<&String> = config.templates.keys().collect();
+    names.sort();
+    names
+        .into_iter()
+        .find(|name| {
+            std::fs::canonicalize(build_target_path(&config.templates[*name], &meta))
+                .map(|target| target == path)
+                .unwrap_or(false)
+        })
+        .cloned()
+}
+
+/// The current unix timestamp
+pub fn now() -> u64 {
+    timestamp(SystemTime::now())
+}
+
+fn timestamp(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0)
+}
+
+/// Format a unix timestamp as a UTC date and time, ie. `2022-06-07 13:45`
+pub fn format_timestamp(secs: u64) -> String {
+    // see: http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let days = (secs / 86400) as i64;
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z.rem_euclid(146097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    let time = secs % 86400;
+    format!("{:04}-{:02}-{:02} {:02}:{:02}", year, month, day, time / 3600, time % 3600 / 60)
+}
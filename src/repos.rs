@@ -0,0 +1,354 @@
+use serde::Serialize;
+use std::{
+    collections::{BTreeMap, HashMap},
+    env,
+    path::{Path, PathBuf},
+};
+
+use crate::{commands::clone::canonical_url, config::AppConfig, registry::Registry, trace};
+
+/// Get the root directory of a template,
+/// ie. the part of the template before the first placeholder.
+///
+/// `~/git/{provider}/{owner}/{repo}` -> `$HOME/git`
+pub fn template_root(template_str: &str) -> PathBuf {
+    let mut root = String::from(template_str.trim());
+    if cfg!(target_family = "unix") {
+        root = root.replace('~', env::var("HOME").unwrap().as_str());
+    }
+    if let Some(idx) = root.find('{') {
+        root.truncate(idx);
+        // drop a partial path segment before the placeholder, eg. `~/git/repo-{repo}`
+        match root.rfind(['/', '\\']) {
+            Some(sep) => root.truncate(sep),
+            None => root.clear(),
+        }
+    }
+    if root.is_empty() {
+        return PathBuf::from(".");
+    }
+    PathBuf::from(root)
+}
+
+/// Find all git repositories under the given root directory.
+///
+/// The search does not descend into repositories, so nested repos
+/// (eg. submodules) are not returned.
+pub fn find_repos(root: &Path) -> Vec<PathBuf> {
+    #[cfg(feature = "logging")]
+    trace!("find_repos: {}", root.display());
+    let mut repos = Vec::new();
+    let mut dirs = vec![root.to_path_buf()];
+    while let Some(dir) = dirs.pop() {
+        if is_repo(&dir) {
+            repos.push(dir);
+            continue;
+        }
+        let entries = match std::fs::read_dir(&dir) {
+            Ok(entries) => entries,
+            Err(_) => continue,
+        };
+        for entry in entries.flatten() {
+            let is_dir = entry.file_type().map(|t| t.is_dir()).unwrap_or(false);
+            if is_dir {
+                dirs.push(entry.path());
+            }
+        }
+    }
+    repos.sort();
+    repos
+}
+
+/// Check if the given directory is the root of a git repository
+pub fn is_repo(dir: &Path) -> bool {
+    dir.join(".git").exists()
+}
+
+/// Get the `origin` URL of a repo, as configured (ie. without `insteadOf` rewrites)
+///
+/// For repos cloned with another remote name, the URL of their [`main_remote`] is used.
+pub fn origin_url(dir: &Path) -> Option<String> {
+    let repo = git2::Repository::open(dir).ok()?;
+    let remote = main_remote(&repo)?;
+    repo.config().ok()?.get_string(&format!("remote.{}.url", remote)).ok()
+}
+
+/// The name of the remote a repo was cloned from: `origin` if there is one,
+/// else the remote the current branch tracks, else the only remote
+pub fn main_remote(repo: &git2::Repository) -> Option<String> {
+    let remotes = repo.remotes().ok()?;
+    let names: Vec<&str> = remotes.iter().flatten().collect();
+    if names.contains(&"origin") {
+        return Some("origin".to_string());
+    }
+    let tracked = repo
+        .head()
+        .ok()
+        .and_then(|head| head.shorthand().map(String::from))
+        .and_then(|branch| repo.config().ok()?.get_string(&format!("branch.{}.remote", branch)).ok());
+    match (tracked, names.as_slice()) {
+        (Some(remote), _) if names.contains(&remote.as_str()) => Some(remote),
+        (_, [only]) => Some(only.to_string()),
+        _ => None,
+    }
+}
+
+/// Find all repos under the roots of the configured templates
+pub fn managed_repos(config: &AppConfig) -> Vec<PathBuf> {
+    let mut roots: Vec<PathBuf> = config.templates.values().map(|t| template_root(t)).collect();
+    roots.sort();
+    roots.dedup();
+    // skip roots nested in other roots, their repos are found already
+    let roots: Vec<&PathBuf> = roots
+        .iter()
+        .filter(|root| !roots.iter().any(|other| other != *root && root.starts_with(other)))
+        .collect();
+    let mut repos: Vec<PathBuf> = roots.into_iter().flat_map(|root| find_repos(root)).collect();
+    repos.sort();
+    repos.dedup();
+    repos
+}
+
+/// Move a repo to `target`, creating its parent directories.
+///
+/// Fails rather than overwriting an existing target.
+pub fn move_repo(path: &Path, target: &Path) -> std::io::Result<()> {
+    #[cfg(feature = "logging")]
+    trace!("move_repo: {} -> {}", path.display(), target.display());
+    if target.exists() {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::AlreadyExists,
+            format!("{} already exists", target.display()),
+        ));
+    }
+    if let Some(parent) = target.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::rename(path, target)
+}
+
+/// Remove the empty parent directories of a removed or moved repo, up to `root`
+pub fn prune_empty_parents(path: &Path, root: &Path) {
+    let mut dir = path.parent();
+    while let Some(parent) = dir {
+        if parent == root || !parent.starts_with(root) || std::fs::remove_dir(parent).is_err() {
+            break;
+        }
+        dir = parent.parent();
+    }
+}
+
+/// Match a path against a template, returning the values of its placeholders,
+/// ie. `~/git/{provider}/{owner}/{repo}` and `$HOME/git/github/acme/api`
+/// -> `provider = github`, `owner = acme`, `repo = api`
+pub fn match_template(template_str: &str, path: &Path) -> Option<HashMap<String, String>> {
+    let mut template = String::from(template_str.trim());
+    if cfg!(target_family = "unix") {
+        template = template.replace('~', env::var("HOME").unwrap().as_str());
+    }
+    let placeholder = regex::Regex::new(r"\{(.*?)\}").unwrap();
+    let mut pattern = String::from("^");
+    let mut keys = Vec::new();
+    let mut last = 0;
+    for cap in placeholder.captures_iter(&template) {
+        let whole = cap.get(0).unwrap();
+        let key = cap.get(1).unwrap().as_str().to_string();
+        pattern.push_str(&regex::escape(&template[last..whole.start()]));
+        if keys.contains(&key) {
+            pattern.push_str(r"[^/\\]+");
+        } else {
+            pattern.push_str(&format!(r"(?P<{}>[^/\\]+)", key));
+            keys.push(key);
+        }
+        last = whole.end();
+    }
+    pattern.push_str(&regex::escape(template[last..].trim_end_matches(['/', '\\'])));
+    pattern.push('$');
+
+    let re = regex::Regex::new(&pattern).ok()?;
+    let path = path.to_string_lossy();
+    let captures = re.captures(&path)?;
+    Some(
+        keys.into_iter()
+            .filter_map(|key| captures.name(&key).map(|value| (key.clone(), value.as_str().to_string())))
+            .collect(),
+    )
+}
+
+/// Find all repos under the template roots and in the registry
+pub fn all_repos(config: &AppConfig) -> Vec<PathBuf> {
+    let mut repos = managed_repos(config);
+    if let Ok(registry) = Registry::load() {
+        repos.extend(
+            registry
+                .repos
+                .iter()
+                .map(|entry| PathBuf::from(&entry.path))
+                .filter(|path| is_repo(path)),
+        );
+    }
+    let mut repos: Vec<PathBuf> = repos
+        .into_iter()
+        .map(|path| std::fs::canonicalize(&path).unwrap_or(path))
+        .collect();
+    repos.sort();
+    repos.dedup();
+    repos
+}
+
+/// The state of a repo's working tree and current branch
+#[derive(Debug, Serialize)]
+pub struct RepoStatus {
+    pub path: PathBuf,
+    /// current branch, `None` when HEAD is detached
+    pub branch: Option<String>,
+    /// upstream of the current branch, ie. `origin/main`
+    pub upstream: Option<String>,
+    /// number of changed tracked files, staged or not
+    pub changed: usize,
+    pub untracked: usize,
+    /// commits not on the upstream, `None` without an upstream
+    pub ahead: Option<usize>,
+    /// upstream commits not on the branch, `None` without an upstream
+    pub behind: Option<usize>,
+    pub stashes: usize,
+}
+
+impl RepoStatus {
+    pub fn new(path: &Path) -> Result<Self, git2::Error> {
+        #[cfg(feature = "logging")]
+        trace!("status: {}", path.display());
+        let mut repo = git2::Repository::open(path)?;
+
+        let mut options = git2::StatusOptions::new();
+        options.include_untracked(true).include_ignored(false).exclude_submodules(true);
+        let (mut changed, mut untracked) = (0, 0);
+        for entry in repo.statuses(Some(&mut options))?.iter() {
+            if entry.status().is_wt_new() {
+                untracked += 1;
+            } else if entry.status() != git2::Status::CURRENT && !entry.status().is_ignored() {
+                changed += 1;
+            }
+        }
+
+        let (mut branch, mut upstream, mut ahead, mut behind) = (None, None, None, None);
+        if let Ok(head) = repo.head() {
+            if head.is_branch() {
+                branch = head.shorthand().map(String::from);
+                let local = git2::Branch::wrap(head);
+                if let Ok(remote) = local.upstream() {
+                    upstream = remote.name().ok().flatten().map(String::from);
+                    if let (Some(local), Some(remote)) = (local.get().target(), remote.get().target()) {
+                        let (a, b) = repo.graph_ahead_behind(local, remote)?;
+                        ahead = Some(a);
+                        behind = Some(b);
+                    }
+                }
+            }
+        } else if let Ok(head) = repo.find_reference("HEAD") {
+            // unborn branch
+            branch = head.symbolic_target().map(|t| t.trim_start_matches("refs/heads/").to_string());
+        }
+
+        let mut stashes = 0;
+        repo.stash_foreach(|_, _, _| {
+            stashes += 1;
+            true
+        })?;
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            branch,
+            upstream,
+            changed,
+            untracked,
+            ahead,
+            behind,
+            stashes,
+        })
+    }
+
+    /// Check for work that only exists locally:
+    /// uncommitted changes, untracked files, unpushed commits or stashes
+    pub fn is_dirty(&self) -> bool {
+        self.changed > 0 || self.untracked > 0 || self.ahead.unwrap_or(0) > 0 || self.stashes > 0
+    }
+}
+
+/// Find the local branches with commits that are not on any remote,
+/// with the number of such commits
+pub fn unpushed_branches(path: &Path) -> Result<Vec<(String, usize)>, git2::Error> {
+    let repo = git2::Repository::open(path)?;
+    let remote_refs: Vec<git2::Oid> = repo
+        .references_glob("refs/remotes/*")?
+        .flatten()
+        .filter_map(|r| r.resolve().ok().and_then(|r| r.target()))
+        .collect();
+    let mut unpushed = Vec::new();
+    for branch in repo.branches(Some(git2::BranchType::Local))? {
+        let (branch, _) = branch?;
+        let oid = match branch.get().target() {
+            Some(oid) => oid,
+            None => continue,
+        };
+        let mut walk = repo.revwalk()?;
+        walk.push(oid)?;
+        for remote in &remote_refs {
+            walk.hide(*remote)?;
+        }
+        let count = walk.count();
+        if count > 0 {
+            let name = branch.name()?.unwrap_or_default().to_string();
+            unpushed.push((name, count));
+        }
+    }
+    Ok(unpushed)
+}
+
+/// Write entries into the local git config of the repo at `path`
+pub fn apply_git_config(path: &Path, entries: &BTreeMap<String, toml::Value>) -> Result<(), git2::Error> {
+    let repo = git2::Repository::open(path)?;
+    let mut config = repo.config()?.open_level(git2::ConfigLevel::Local)?;
+    for (key, value) in entries {
+        #[cfg(feature = "logging")]
+        trace!("git config {} = {} in {}", key, value, path.display());
+        match value {
+            toml::Value::Boolean(value) => config.set_bool(key, *value)?,
+            toml::Value::Integer(value) => config.set_i64(key, *value)?,
+            toml::Value::String(value) => config.set_str(key, value)?,
+            value => config.set_str(key, &value.to_string())?,
+        }
+    }
+    Ok(())
+}
+
+/// Find the name of the configured template a repo is laid out with
+pub fn template_of(config: &AppConfig, path: &Path) -> Option<String> {
+    let mut names: Vec<&String> = config.templates.keys().collect();
+    names.sort();
+    names
+        .into_iter()
+        .find(|name| match_template(&config.templates[*name], path).is_some())
+        .cloned()
+}
+
+/// Check if a repo URL matches a pattern.
+///
+/// The pattern is matched against the canonical URL (`host/owner/repo`),
+/// `*` matches any characters, otherwise any substring matches.
+pub fn matches_pattern(pattern: &str, url: &str) -> bool {
+    let url = canonical_url(url);
+    let pattern = pattern.to_lowercase();
+    if pattern.contains('*') {
+        let re = pattern
+            .split('*')
+            .map(regex::escape)
+            .collect::<Vec<_>>()
+            .join(".*");
+        regex::Regex::new(&format!("^{}$", re))
+            .map(|re| re.is_match(&url))
+            .unwrap_or(false)
+    } else {
+        url.contains(&pattern)
+    }
+}
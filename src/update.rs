@@ -0,0 +1,131 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+};
+
+use crate::{
+    commands::clone::{CloneOptions, INTERRUPTED},
+    repos::{self, RepoStatus},
+    trace,
+};
+
+/// The result of fetching or pulling a repo
+#[derive(Debug)]
+pub enum Outcome {
+    Updated,
+    UpToDate,
+    /// the current branch and its upstream both have commits the other lacks
+    Diverged,
+    /// left alone, with the reason
+    Skipped(String),
+    Failed(String),
+}
+
+/// Fetch `origin` of the repo at `path`, pruning deleted branches
+pub fn fetch(path: &Path, origin: &str) -> Outcome {
+    #[cfg(feature = "logging")]
+    trace!("fetch: {}", path.display());
+    let before = match remote_refs(path) {
+        Ok(refs) => refs,
+        Err(e) => return Outcome::Failed(e.message().to_string()),
+    };
+    let mut options = CloneOptions::for_checkout(path, origin);
+    options.prune = true;
+    if let Err(e) = options.git_fetch() {
+        return Outcome::Failed(e.to_string());
+    }
+    let changed = remote_refs(path).map(|after| after != before).unwrap_or(true);
+    match RepoStatus::new(path) {
+        Ok(status) if status.ahead.unwrap_or(0) > 0 && status.behind.unwrap_or(0) > 0 => Outcome::Diverged,
+        _ if changed => Outcome::Updated,
+        _ => Outcome::UpToDate,
+    }
+}
+
+/// Fetch `origin` of the repo at `path` and fast-forward the current branch,
+/// leaving repos with local changes alone
+pub fn pull(path: &Path, origin: &str) -> Outcome {
+    #[cfg(feature = "logging")]
+    trace!("pull: {}", path.display());
+    match RepoStatus::new(path) {
+        Err(e) => return Outcome::Failed(e.message().to_string()),
+        Ok(status) if status.changed > 0 || status.untracked > 0 => {
+            return Outcome::Skipped("dirty working tree".to_string())
+        }
+        Ok(status) if status.branch.is_none() => return Outcome::Skipped("HEAD is detached".to_string()),
+        Ok(_) => {}
+    }
+
+    let mut options = CloneOptions::for_checkout(path, origin);
+    options.prune = true;
+    if let Err(e) = options.git_fetch() {
+        return Outcome::Failed(e.to_string());
+    }
+    let status = match RepoStatus::new(path) {
+        Ok(status) => status,
+        Err(e) => return Outcome::Failed(e.message().to_string()),
+    };
+    match (status.ahead, status.behind) {
+        (None, _) | (_, None) => Outcome::Skipped("no upstream branch".to_string()),
+        (_, Some(0)) => Outcome::UpToDate,
+        (Some(ahead), _) if ahead > 0 => Outcome::Diverged,
+        _ => {
+            let result = git2::Repository::open(path)
+                .map_err(Into::into)
+                .and_then(|repo| options.fast_forward(&repo));
+            match result {
+                Ok(()) => Outcome::Updated,
+                Err(e) => Outcome::Failed(e.to_string()),
+            }
+        }
+    }
+}
+
+/// Run `f` on every repo, given by path and `origin` URL, with up to `jobs` threads.
+/// The outcomes are returned in the order of the repos.
+pub fn run_parallel<F>(repos: &[(PathBuf, String)], jobs: usize, f: F) -> Vec<Outcome>
+where
+    F: Fn(&Path, &str) -> Outcome + Sync,
+{
+    let next = AtomicUsize::new(0);
+    let results: Mutex<Vec<Option<Outcome>>> = Mutex::new(repos.iter().map(|_| None).collect());
+    std::thread::scope(|scope| {
+        for _ in 0..jobs.clamp(1, repos.len().max(1)) {
+            scope.spawn(|| loop {
+                let idx = next.fetch_add(1, Ordering::SeqCst);
+                if idx >= repos.len() {
+                    break;
+                }
+                let (path, origin) = &repos[idx];
+                let outcome = if INTERRUPTED.load(Ordering::SeqCst) {
+                    Outcome::Failed("interrupted".to_string())
+                } else {
+                    f(path, origin)
+                };
+                results.lock().unwrap()[idx] = Some(outcome);
+            });
+        }
+    });
+    results
+        .into_inner()
+        .unwrap()
+        .into_iter()
+        .map(|outcome| outcome.unwrap_or_else(|| Outcome::Failed("not run".to_string())))
+        .collect()
+}
+
+/// The refs of the main remote of a repo, to tell if a fetch changed anything
+fn remote_refs(path: &Path) -> Result<Vec<(String, Option<git2::Oid>)>, git2::Error> {
+    let repo = git2::Repository::open(path)?;
+    let remote = repos::main_remote(&repo).unwrap_or_else(|| "origin".to_string());
+    let refs = repo.references_glob(&format!("refs/remotes/{}/*", remote))?;
+    let mut refs: Vec<(String, Option<git2::Oid>)> = refs
+        .flatten()
+        .map(|r| (r.name().unwrap_or_default().to_string(), r.target()))
+        .collect();
+    refs.sort();
+    Ok(refs)
+}
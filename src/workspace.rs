@@ -0,0 +1,179 @@
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use crate::commands::clone::CloneOptions;
+
+/// Default file name of a workspace manifest
+pub const MANIFEST_FILE: &str = "workspace.toml";
+
+/// Get the path of the lockfile that belongs to a manifest,
+/// ie. `workspace.toml` -> `workspace.lock`
+pub fn lockfile_path(manifest_path: &Path) -> PathBuf {
+    manifest_path.with_extension("lock")
+}
+
+/// A workspace manifest, listing the repos that make up a workspace.
+///
+/// ```toml
+/// template = "work"
+///
+/// [[repos]]
+/// url = "git@github.com:acme/api.git"
+/// branch = "main"
+/// depth = 1
+///
+/// [[repos]]
+/// url = "acme/web"
+/// template = "~/src/{owner}/{repo}"
+/// tags = false
+/// ```
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Manifest {
+    /// template used for repos that don't set one,
+    /// falls back to the default template from the config
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub template: Option<String>,
+    #[serde(default)]
+    pub repos: Vec<ManifestRepo>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ManifestRepo {
+    /// The Git repository, in any form accepted by `gclone clone`
+    pub url: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub template: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub branch: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub depth: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tags: Option<bool>,
+}
+
+impl Manifest {
+    pub fn load(path: &Path) -> Result<Self, Box<dyn std::error::Error>> {
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| format!("unable to read manifest {}: {}", path.display(), e))?;
+        let manifest = toml::from_str(&contents)
+            .map_err(|e| format!("invalid manifest {}: {}", path.display(), e))?;
+        Ok(manifest)
+    }
+
+    /// Get the template name or string to use for a repo in this manifest
+    pub fn template_for(&self, repo: &ManifestRepo, default_template: &str) -> String {
+        repo.template
+            .clone()
+            .or_else(|| self.template.clone())
+            .unwrap_or_else(|| default_template.to_string())
+    }
+
+    /// Build the clone options for a repo in this manifest
+    pub fn clone_options(&self, repo: &ManifestRepo, default_template: &str) -> Result<CloneOptions, Box<dyn std::error::Error>> {
+        let template = self.template_for(repo, default_template);
+        let mut options = CloneOptions::new(
+            repo.url.clone(),
+            &template,
+            repo.branch.clone(),
+            false,
+            None,
+            None,
+            None,
+        )?;
+        options.depth = repo.depth;
+        options.tags = repo.tags;
+        Ok(options)
+    }
+}
+
+/// A lockfile, pinning every repo of a workspace to a commit.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Lockfile {
+    #[serde(default)]
+    pub repos: Vec<LockedRepo>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LockedRepo {
+    /// The remote URL the repo is cloned from
+    pub url: String,
+    /// The templated path of the repo
+    pub path: String,
+    /// The pinned commit
+    pub commit: String,
+}
+
+impl Lockfile {
+    pub fn load(path: &Path) -> Result<Self, Box<dyn std::error::Error>> {
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| format!("unable to read lockfile {}: {}", path.display(), e))?;
+        let lockfile = toml::from_str(&contents)
+            .map_err(|e| format!("invalid lockfile {}: {}", path.display(), e))?;
+        Ok(lockfile)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+        let contents = toml::to_string_pretty(self)?;
+        std::fs::write(path, contents)
+            .map_err(|e| format!("unable to write lockfile {}: {}", path.display(), e))?;
+        Ok(())
+    }
+
+    /// Find the locked entry for a remote URL
+    pub fn find(&self, url: &str) -> Option<&LockedRepo> {
+        self.repos.iter().find(|r| r.url == url)
+    }
+}
+
+impl LockedRepo {
+    /// Lock a cloned repo to its current HEAD commit
+    pub fn from_head(url: &str, path: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let repo = git2::Repository::open(path)
+            .map_err(|e| format!("{} is not cloned, run `gclone sync` first: {}", path, e.message()))?;
+        let commit = repo.head()?.peel_to_commit()?;
+        Ok(Self {
+            url: url.to_string(),
+            path: path.to_string(),
+            commit: commit.id().to_string(),
+        })
+    }
+
+    /// Check out the pinned commit in the repo at `path`, leaving HEAD detached.
+    ///
+    /// The commit must be reachable from a remote branch or a tag, so a commit that
+    /// only survives locally after upstream force-pushed it away is reported.
+    pub fn checkout(&self, path: &str) -> Result<(), Box<dyn std::error::Error>> {
+        crate::trace!("checkout {} in {}", &self.commit, path);
+        let repo = git2::Repository::open(path)?;
+        let oid = git2::Oid::from_str(&self.commit)?;
+        let unreachable = || {
+            format!(
+                "pinned commit {} of {} is no longer reachable from the remote",
+                &self.commit, &self.url
+            )
+        };
+        let commit = repo.find_commit(oid).map_err(|_| unreachable())?;
+        if !is_reachable(&repo, oid)? {
+            return Err(unreachable().into());
+        }
+        repo.checkout_tree(commit.as_object(), Some(git2::build::CheckoutBuilder::new().safe()))?;
+        repo.set_head_detached(oid)?;
+        Ok(())
+    }
+}
+
+/// Check if a commit is reachable from any remote-tracking branch or tag
+fn is_reachable(repo: &git2::Repository, oid: git2::Oid) -> Result<bool, git2::Error> {
+    for glob in ["refs/remotes/*", "refs/tags/*"] {
+        for reference in repo.references_glob(glob)?.flatten() {
+            let tip = match reference.peel_to_commit() {
+                Ok(commit) => commit.id(),
+                Err(_) => continue,
+            };
+            if tip == oid || repo.graph_descendant_of(tip, oid)? {
+                return Ok(true);
+            }
+        }
+    }
+    Ok(false)
+}